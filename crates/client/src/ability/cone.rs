@@ -0,0 +1,172 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::prelude::Added;
+use bevy::prelude::Assets;
+use bevy::prelude::BuildChildren;
+use bevy::prelude::ChildBuild;
+use bevy::prelude::Children;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Query;
+use bevy::prelude::RemovedComponents;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::Transform;
+use bevy::prelude::Vec3;
+use bevy::prelude::Vec4;
+use bevy::prelude::With;
+use bevy::prelude::World;
+use bevy_hanabi::Attribute;
+use bevy_hanabi::ColorOverLifetimeModifier;
+use bevy_hanabi::EffectAsset;
+use bevy_hanabi::ExprWriter;
+use bevy_hanabi::Gradient;
+use bevy_hanabi::ParticleEffectBundle;
+use bevy_hanabi::SetAttributeModifier;
+use bevy_hanabi::SetPositionSphereModifier;
+use bevy_hanabi::SetVelocitySphereModifier;
+use bevy_hanabi::ShapeDimension;
+use bevy_hanabi::SimulationSpace;
+use bevy_hanabi::SizeOverLifetimeModifier;
+use bevy_hanabi::Spawner;
+use engine::ability::cone::ConeKind;
+use engine::ability::cone::ConeProps;
+use engine::ability::cone::ConeSpray;
+use engine::ability::cone::Cryo;
+use engine::ability::cone::Flamethrower;
+use engine::AbilityOffset;
+use engine::FORWARD;
+
+/// How long each particle lives, in seconds.
+const LIFETIME: f32 = 0.4;
+
+/// How far in front of the emitter particles spawn. Along with the spread of
+/// where they spawn, this sets the angle of the cone.
+const NOZZLE: f32 = 0.1;
+
+pub struct ConePlugin;
+impl Plugin for ConePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, (draw_spray_system, remove_spray_system));
+    }
+}
+
+#[derive(Resource)]
+struct ConeAssets {
+    flame_effect: Handle<EffectAsset>,
+    cryo_effect: Handle<EffectAsset>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut effects: ResMut<Assets<EffectAsset>>,
+    flame_props: Res<ConeProps<Flamethrower>>,
+    cryo_props: Res<ConeProps<Cryo>>,
+) {
+    let mut flame_gradient = Gradient::new();
+    flame_gradient.add_key(0.0, Vec4::new(8.0, 6.0, 1.0, 1.0));
+    flame_gradient.add_key(0.5, Vec4::new(8.0, 2.0, 0.0, 1.0));
+    flame_gradient.add_key(1.0, Vec4::new(2.0, 0.0, 0.0, 0.0));
+
+    let mut cryo_gradient = Gradient::new();
+    cryo_gradient.add_key(0.0, Vec4::new(4.0, 6.0, 8.0, 1.0));
+    cryo_gradient.add_key(0.5, Vec4::new(1.0, 4.0, 8.0, 1.0));
+    cryo_gradient.add_key(1.0, Vec4::new(0.0, 1.0, 4.0, 0.0));
+
+    let assets = ConeAssets {
+        flame_effect: effects.add(cone_effect(&flame_props, flame_gradient)),
+        cryo_effect: effects.add(cone_effect(&cryo_props, cryo_gradient)),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+/// A stream of particles filling the cone, relative to the emitter.
+fn cone_effect<K: ConeKind>(props: &ConeProps<K>, gradient: Gradient<Vec4>) -> EffectAsset {
+    let spawner = Spawner::rate(400.0.into());
+    let writer = ExprWriter::new();
+
+    // Particles move away from the emitter, so those spawned at the edge of
+    // this sphere go out at the edge of the cone.
+    let pos = SetPositionSphereModifier {
+        center: writer.lit(FORWARD * NOZZLE).expr(),
+        radius: writer.lit(NOZZLE * (props.arc * 0.5).sin()).expr(),
+        dimension: ShapeDimension::Volume,
+    };
+
+    let vel = SetVelocitySphereModifier {
+        center: writer.lit(Vec3::ZERO).expr(),
+        speed: writer.lit(props.range / LIFETIME).expr(),
+    };
+
+    let lifetime = SetAttributeModifier {
+        attribute: Attribute::LIFETIME,
+        value: writer.lit(LIFETIME).expr(),
+    };
+
+    let mut size_gradient = Gradient::new();
+    size_gradient.add_key(0.0, Vec3::splat(0.03));
+    size_gradient.add_key(1.0, Vec3::splat(0.12));
+
+    EffectAsset::new(32768, spawner, writer.finish())
+        .with_name("cone_effect")
+        .with_simulation_space(SimulationSpace::Local)
+        .init(pos)
+        .init(vel)
+        .init(lifetime)
+        .render(ColorOverLifetimeModifier { gradient })
+        .render(SizeOverLifetimeModifier {
+            gradient: size_gradient,
+            screen_space_size: false,
+        })
+}
+
+#[derive(Component)]
+struct Spray;
+
+fn draw_spray_system(
+    mut commands: Commands,
+    assets: Res<ConeAssets>,
+    query: Query<(Entity, &ConeSpray, &AbilityOffset), Added<ConeSpray>>,
+) {
+    for (entity, spray, ability_offset) in &query {
+        let effect = if spray.heat < 0.0 {
+            assets.cryo_effect.clone_weak()
+        } else {
+            assets.flame_effect.clone_weak()
+        };
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                ParticleEffectBundle {
+                    transform: Transform::from_translation(ability_offset.to_vec()),
+                    ..ParticleEffectBundle::new(effect)
+                },
+                Spray,
+            ));
+        });
+    }
+}
+
+fn remove_spray_system(
+    mut commands: Commands,
+    mut removed: RemovedComponents<ConeSpray>,
+    children_q: Query<&Children>,
+    spray_q: Query<(), With<Spray>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children_q.get(entity) else {
+            continue;
+        };
+        for &child in children {
+            if spray_q.get(child).is_ok() {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+}
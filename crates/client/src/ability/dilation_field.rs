@@ -0,0 +1,91 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::Color;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::Added;
+use bevy::prelude::AlphaMode;
+use bevy::prelude::Assets;
+use bevy::prelude::BuildChildren;
+use bevy::prelude::ChildBuild;
+use bevy::prelude::Commands;
+use bevy::prelude::Cylinder;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Transform;
+use bevy::prelude::Vec3;
+use bevy::prelude::World;
+use engine::ability::dilation_field::DilationField;
+
+/// How tall the disc we draw for a field is.
+const FIELD_HEIGHT: f32 = 0.02;
+
+pub struct DilationFieldPlugin;
+impl Plugin for DilationFieldPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, draw_field_system);
+    }
+}
+
+#[derive(Resource)]
+struct DilationFieldAssets {
+    mesh: Handle<Mesh>,
+    slow_material: Handle<StandardMaterial>,
+    fast_material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = DilationFieldAssets {
+        mesh: meshes.add(Cylinder::new(1.0, 1.0)),
+        slow_material: materials.add(StandardMaterial {
+            base_color: Color::linear_rgba(0.3, 0.2, 0.8, 0.3),
+            emissive: LinearRgba::rgb(0.3, 0.1, 1.0),
+            alpha_mode: AlphaMode::Blend,
+            ..Default::default()
+        }),
+        fast_material: materials.add(StandardMaterial {
+            base_color: Color::linear_rgba(1.0, 0.9, 0.3, 0.3),
+            emissive: LinearRgba::rgb(1.0, 0.8, 0.1),
+            alpha_mode: AlphaMode::Blend,
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+fn draw_field_system(
+    mut commands: Commands,
+    assets: Res<DilationFieldAssets>,
+    query: Query<(Entity, &DilationField), Added<DilationField>>,
+) {
+    for (entity, field) in &query {
+        let material = if field.amount < 0.0 {
+            &assets.slow_material
+        } else {
+            &assets.fast_material
+        };
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                MeshMaterial3d::from(material.clone_weak()),
+                Mesh3d::from(assets.mesh.clone_weak()),
+                Transform::from_scale(Vec3::new(field.radius, FIELD_HEIGHT, field.radius)),
+                NotShadowCaster,
+            ));
+        });
+    }
+}
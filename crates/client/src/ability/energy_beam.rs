@@ -0,0 +1,150 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::Added;
+use bevy::prelude::Assets;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::Cuboid;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::Sphere;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Time;
+use bevy::prelude::Transform;
+use bevy::prelude::Vec3;
+use bevy::prelude::Without;
+use bevy::prelude::World;
+use engine::ability::energy_beam::EnergyBeam;
+use engine::AbilityOffset;
+use engine::UP;
+
+/// How thick the beam we draw is.
+const BEAM_WIDTH: f32 = 0.02;
+
+/// The pulses travelling along the beam show which way energy is flowing.
+const PULSES: usize = 4;
+const PULSE_RADIUS: f32 = 0.04;
+/// How many times per second a pulse crosses the beam.
+const PULSE_RATE: f32 = 1.5;
+
+pub struct EnergyBeamPlugin;
+impl Plugin for EnergyBeamPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, (draw_beam_system, update_beam_system));
+    }
+}
+
+#[derive(Resource)]
+struct EnergyBeamAssets {
+    beam_mesh: Handle<Mesh>,
+    pulse_mesh: Handle<Mesh>,
+    give_material: Handle<StandardMaterial>,
+    steal_material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = EnergyBeamAssets {
+        beam_mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        pulse_mesh: meshes.add(Sphere::new(PULSE_RADIUS)),
+        give_material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(2.0, 6.0, 20.0),
+            ..Default::default()
+        }),
+        steal_material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(12.0, 2.0, 16.0),
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+/// Part of the beam between a user and their target.
+///
+/// It isn't a child of either, as it needs to follow both.
+#[derive(Component)]
+struct BeamVisual {
+    user: Entity,
+    /// For pulses, how far along the beam they start; `None` for the beam
+    /// itself.
+    pulse: Option<f32>,
+}
+
+fn draw_beam_system(
+    mut commands: Commands,
+    assets: Res<EnergyBeamAssets>,
+    query: Query<(Entity, &EnergyBeam), Added<EnergyBeam>>,
+) {
+    for (user, beam) in &query {
+        let material = if beam.stealing {
+            &assets.steal_material
+        } else {
+            &assets.give_material
+        };
+        commands.spawn((
+            MeshMaterial3d::from(material.clone_weak()),
+            Mesh3d::from(assets.beam_mesh.clone_weak()),
+            Transform::default().with_scale(Vec3::ZERO),
+            BeamVisual { user, pulse: None },
+        ));
+        for i in 0..PULSES {
+            commands.spawn((
+                MeshMaterial3d::from(material.clone_weak()),
+                Mesh3d::from(assets.pulse_mesh.clone_weak()),
+                Transform::default().with_scale(Vec3::ZERO),
+                BeamVisual {
+                    user,
+                    pulse: Some(i as f32 / PULSES as f32),
+                },
+            ));
+        }
+    }
+}
+
+fn update_beam_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    user_q: Query<(&EnergyBeam, &Transform, &AbilityOffset), Without<BeamVisual>>,
+    target_q: Query<&Transform, Without<BeamVisual>>,
+    mut beam_q: Query<(Entity, &BeamVisual, &mut Transform)>,
+) {
+    for (entity, visual, mut transform) in &mut beam_q {
+        let Ok((beam, user_transform, ability_offset)) = user_q.get(visual.user) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        let Ok(target_transform) = target_q.get(beam.target) else {
+            continue;
+        };
+        let mut start = user_transform.translation + ability_offset.to_vec();
+        let mut end = target_transform.translation;
+        if beam.stealing {
+            std::mem::swap(&mut start, &mut end);
+        }
+
+        *transform = match visual.pulse {
+            None => Transform::from_translation((start + end) * 0.5)
+                .looking_at(end, UP)
+                .with_scale(Vec3::new(BEAM_WIDTH, BEAM_WIDTH, start.distance(end))),
+            Some(offset) => {
+                let along = (time.elapsed_secs() * PULSE_RATE + offset).fract();
+                Transform::from_translation(start.lerp(end, along))
+            }
+        };
+    }
+}
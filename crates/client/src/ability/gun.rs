@@ -8,6 +8,7 @@ use bevy::color::LinearRgba;
 use bevy::core::FrameCount;
 use bevy::ecs::entity::Entity;
 use bevy::ecs::query::Added;
+use bevy::ecs::query::Has;
 use bevy::ecs::system::Commands;
 use bevy::ecs::system::Query;
 use bevy::ecs::system::Res;
@@ -47,6 +48,7 @@ use bevy_kira_audio::AudioSource;
 use engine::ability::bullet::Bullet;
 use engine::ability::gun::GunKind;
 use engine::ability::gun::GunProps;
+use engine::ability::gun::Shotgun;
 use engine::ability::gun::StandardGun;
 use engine::lifecycle::ClientDeathCallback;
 
@@ -61,7 +63,10 @@ struct BulletAssets {
     standard_material: Handle<StandardMaterial>,
     fire_material: Handle<StandardMaterial>,
     cold_material: Handle<StandardMaterial>,
+    shock_material: Handle<StandardMaterial>,
+    shotgun_material: Handle<StandardMaterial>,
     collision_effect: ParticleEffectPool,
+    shotgun_collision_effect: ParticleEffectPool,
     spawn_sound: Handle<AudioSource>,
     despawn_sound: Handle<AudioSource>,
 }
@@ -89,11 +94,14 @@ fn setup(
     mut effects: ResMut<Assets<EffectAsset>>,
     asset_server: ResMut<AssetServer>,
     standard_props: Res<GunProps<StandardGun>>,
+    shotgun_props: Res<GunProps<Shotgun>>,
     // fire_props: Res<GunProps<FireGun>>,
     // cold_props: Res<GunProps<ColdGun>>,
 ) {
     let effect = effects.add(bullet_effect(&standard_props));
     let effect_pool = ParticleEffectBundle::new(effect).into();
+    let shotgun_effect = effects.add(bullet_effect(&shotgun_props));
+    let shotgun_effect_pool = ParticleEffectBundle::new(shotgun_effect).into();
 
     let standard_material = StandardMaterial {
         base_color: Color::linear_rgb(0.2, 0.2, 0.2),
@@ -110,12 +118,25 @@ fn setup(
         ..Default::default()
     };
 
+    let shock_material = StandardMaterial {
+        emissive: LinearRgba::rgb(15.0, 15.0, 5.0),
+        ..Default::default()
+    };
+
+    let shotgun_material = StandardMaterial {
+        base_color: Color::linear_rgb(0.4, 0.3, 0.1),
+        ..Default::default()
+    };
+
     let bullet = BulletAssets {
         mesh: meshes.add(Sphere::new(1.0)),
         standard_material: materials.add(standard_material),
         fire_material: materials.add(fire_material),
         cold_material: materials.add(cold_material),
+        shock_material: materials.add(shock_material),
+        shotgun_material: materials.add(shotgun_material),
         collision_effect: effect_pool,
+        shotgun_collision_effect: shotgun_effect_pool,
         spawn_sound: asset_server.load("third-party/audio/other/laserSmall_000.ogg"),
         despawn_sound: asset_server.load("third-party/audio/other/laserSmall_000.ogg"),
     };
@@ -125,7 +146,7 @@ fn setup(
 
 fn bullet_death_system(
     In(entity): In<Entity>,
-    query: Query<&Transform, Without<EffectInitializers>>,
+    query: Query<(&Transform, Has<Shotgun>), Without<EffectInitializers>>,
     mut commands: Commands,
     mut assets: ResMut<BulletAssets>,
     audio: Res<Audio>,
@@ -133,8 +154,12 @@ fn bullet_death_system(
     mut effects: Query<(&mut Transform, &mut EffectInitializers)>,
     frame: Res<FrameCount>,
 ) {
-    let effect = &mut assets.collision_effect;
-    let transform = *query.get(entity).unwrap();
+    let (&transform, shotgun) = query.get(entity).unwrap();
+    let effect = if shotgun {
+        &mut assets.shotgun_collision_effect
+    } else {
+        &mut assets.collision_effect
+    };
     effect.trigger(&mut commands, transform, &mut effects, &frame);
 
     let sound = assets.despawn_sound.clone_weak();
@@ -147,16 +172,20 @@ fn draw_bullet(
     mut commands: Commands,
     assets: Res<BulletAssets>,
     death_callback: Res<GunDeathCallback>,
-    query: Query<(Entity, &Bullet), Added<Bullet>>,
+    query: Query<(Entity, &Bullet, Has<Shotgun>), Added<Bullet>>,
 ) {
-    for (entity, bullet) in query.iter() {
+    for (entity, bullet, shotgun) in query.iter() {
         let Some(mut ecmds) = commands.get_entity(entity) else {
             continue;
         };
-        let material = if bullet.heat > 0.0 {
+        let material = if shotgun {
+            assets.shotgun_material.clone_weak()
+        } else if bullet.heat > 0.0 {
             assets.fire_material.clone_weak()
         } else if bullet.heat < 0.0 {
             assets.cold_material.clone_weak()
+        } else if bullet.charge != 0.0 {
+            assets.shock_material.clone_weak()
         } else {
             assets.standard_material.clone_weak()
         };
@@ -0,0 +1,102 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::Added;
+use bevy::prelude::Assets;
+use bevy::prelude::BuildChildren;
+use bevy::prelude::ChildBuild;
+use bevy::prelude::Children;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::Cylinder;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::RemovedComponents;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Transform;
+use bevy::prelude::With;
+use bevy::prelude::World;
+use engine::ability::head::Revealed;
+use engine::level::WALL_HEIGHT;
+use engine::PLAYER_R;
+
+pub struct HeadPlugin;
+impl Plugin for HeadPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, (draw_revealed_system, remove_revealed_system));
+    }
+}
+
+#[derive(Resource)]
+struct HeadAssets {
+    reveal_mesh: Handle<Mesh>,
+    reveal_material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = HeadAssets {
+        reveal_mesh: meshes.add(Cylinder::new(PLAYER_R * 1.2, 0.02)),
+        reveal_material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(20.0, 4.0, 2.0),
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+/// A marker over a revealed character. It floats above the walls, so it can be
+/// seen through them.
+#[derive(Component)]
+struct RevealMarker;
+
+fn draw_revealed_system(
+    mut commands: Commands,
+    assets: Res<HeadAssets>,
+    query: Query<Entity, Added<Revealed>>,
+) {
+    for entity in &query {
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                MeshMaterial3d::from(assets.reveal_material.clone_weak()),
+                Mesh3d::from(assets.reveal_mesh.clone_weak()),
+                Transform::from_xyz(0.0, WALL_HEIGHT, 0.0),
+                NotShadowCaster,
+                RevealMarker,
+            ));
+        });
+    }
+}
+
+fn remove_revealed_system(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Revealed>,
+    children_q: Query<&Children>,
+    marker_q: Query<(), With<RevealMarker>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children_q.get(entity) else {
+            continue;
+        };
+        for &child in children {
+            if marker_q.get(child).is_ok() {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::Added;
+use bevy::prelude::Assets;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::Cuboid;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Transform;
+use bevy::prelude::Vec3;
+use bevy::prelude::Without;
+use bevy::prelude::World;
+use engine::ability::heal_beam::HealBeam;
+use engine::AbilityOffset;
+use engine::UP;
+
+/// How thick the beam we draw is.
+const BEAM_WIDTH: f32 = 0.04;
+
+pub struct HealBeamPlugin;
+impl Plugin for HealBeamPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, (draw_beam_system, update_beam_system));
+    }
+}
+
+#[derive(Resource)]
+struct HealBeamAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = HealBeamAssets {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(2.0, 20.0, 4.0),
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+/// The beam between a healer and their target.
+///
+/// It isn't a child of either, as it needs to follow both.
+#[derive(Component)]
+struct BeamVisual {
+    healer: Entity,
+}
+
+fn beam_transform(start: Vec3, end: Vec3) -> Transform {
+    let length = start.distance(end);
+    Transform::from_translation((start + end) * 0.5)
+        .looking_at(end, UP)
+        .with_scale(Vec3::new(BEAM_WIDTH, BEAM_WIDTH, length))
+}
+
+fn draw_beam_system(
+    mut commands: Commands,
+    assets: Res<HealBeamAssets>,
+    query: Query<Entity, Added<HealBeam>>,
+) {
+    for healer in &query {
+        commands.spawn((
+            MeshMaterial3d::from(assets.material.clone_weak()),
+            Mesh3d::from(assets.mesh.clone_weak()),
+            Transform::default().with_scale(Vec3::ZERO),
+            BeamVisual { healer },
+        ));
+    }
+}
+
+fn update_beam_system(
+    mut commands: Commands,
+    healer_q: Query<(&HealBeam, &Transform, &AbilityOffset), Without<BeamVisual>>,
+    target_q: Query<&Transform, Without<BeamVisual>>,
+    mut beam_q: Query<(Entity, &BeamVisual, &mut Transform)>,
+) {
+    for (entity, visual, mut transform) in &mut beam_q {
+        let Ok((beam, healer_transform, ability_offset)) = healer_q.get(visual.healer) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+        let Ok(target_transform) = target_q.get(beam.target) else {
+            continue;
+        };
+        let start = healer_transform.translation + ability_offset.to_vec();
+        let end = target_transform.translation;
+        *transform = beam_transform(start, end);
+    }
+}
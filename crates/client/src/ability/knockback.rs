@@ -0,0 +1,64 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::Added;
+use bevy::prelude::Assets;
+use bevy::prelude::Commands;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::Sphere;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::World;
+use engine::ability::knockback::Hook;
+
+pub struct KnockbackPlugin;
+impl Plugin for KnockbackPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup)
+            .add_systems(Update, draw_hook_system);
+    }
+}
+
+#[derive(Resource)]
+struct HookAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = HookAssets {
+        mesh: meshes.add(Sphere::new(1.0)),
+        material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(3.0, 2.0, 0.5),
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+fn draw_hook_system(
+    mut commands: Commands,
+    assets: Res<HookAssets>,
+    query: Query<Entity, Added<Hook>>,
+) {
+    for entity in &query {
+        // The hook's scale is its radius, so a unit sphere fits it.
+        commands.entity(entity).insert((
+            MeshMaterial3d::from(assets.material.clone_weak()),
+            Mesh3d::from(assets.mesh.clone_weak()),
+        ));
+    }
+}
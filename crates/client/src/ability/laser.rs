@@ -0,0 +1,126 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::Added;
+use bevy::prelude::Assets;
+use bevy::prelude::BuildChildren;
+use bevy::prelude::ChildBuild;
+use bevy::prelude::Children;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::Cuboid;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::RemovedComponents;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Transform;
+use bevy::prelude::Vec3;
+use bevy::prelude::With;
+use bevy::prelude::World;
+use engine::ability::laser::LaserBeam;
+use engine::AbilityOffset;
+use engine::FORWARD;
+
+/// How thick the beam we draw is.
+const BEAM_WIDTH: f32 = 0.03;
+
+pub struct LaserPlugin;
+impl Plugin for LaserPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup).add_systems(
+            Update,
+            (draw_beam_system, update_beam_system, remove_beam_system),
+        );
+    }
+}
+
+#[derive(Resource)]
+struct LaserAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = LaserAssets {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(20.0, 2.0, 2.0),
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+#[derive(Component)]
+struct Beam;
+
+/// Where the beam is, relative to the user; it ends at whatever it hit.
+fn beam_transform(beam: &LaserBeam, ability_offset: &AbilityOffset) -> Transform {
+    Transform {
+        translation: FORWARD * beam.length * 0.5 + ability_offset.to_vec(),
+        scale: Vec3::new(BEAM_WIDTH, BEAM_WIDTH, beam.length),
+        ..Default::default()
+    }
+}
+
+fn draw_beam_system(
+    mut commands: Commands,
+    assets: Res<LaserAssets>,
+    query: Query<(Entity, &LaserBeam, &AbilityOffset), Added<LaserBeam>>,
+) {
+    for (entity, beam, ability_offset) in &query {
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                MeshMaterial3d::from(assets.material.clone_weak()),
+                Mesh3d::from(assets.mesh.clone_weak()),
+                beam_transform(beam, ability_offset),
+                Beam,
+            ));
+        });
+    }
+}
+
+fn update_beam_system(
+    laser_q: Query<(&LaserBeam, &AbilityOffset, &Children)>,
+    mut beam_q: Query<&mut Transform, With<Beam>>,
+) {
+    for (beam, ability_offset, children) in &laser_q {
+        for &child in children {
+            if let Ok(mut transform) = beam_q.get_mut(child) {
+                *transform = beam_transform(beam, ability_offset);
+            }
+        }
+    }
+}
+
+fn remove_beam_system(
+    mut commands: Commands,
+    mut removed: RemovedComponents<LaserBeam>,
+    children_q: Query<&Children>,
+    beam_q: Query<(), With<Beam>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children_q.get(entity) else {
+            continue;
+        };
+        for &child in children {
+            if beam_q.get(child).is_ok() {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::Added;
+use bevy::prelude::Assets;
+use bevy::prelude::BuildChildren;
+use bevy::prelude::ChildBuild;
+use bevy::prelude::Children;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::Cuboid;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Quat;
+use bevy::prelude::Query;
+use bevy::prelude::RemovedComponents;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Transform;
+use bevy::prelude::Vec3;
+use bevy::prelude::With;
+use bevy::prelude::World;
+use engine::ability::melee::Swing;
+use engine::AbilityOffset;
+use engine::FORWARD;
+use engine::PLAYER_R;
+
+/// How thick the blade we draw is.
+const BLADE_WIDTH: f32 = 0.04;
+
+pub struct MeleePlugin;
+impl Plugin for MeleePlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup).add_systems(
+            Update,
+            (draw_swing_system, update_swing_system, remove_swing_system),
+        );
+    }
+}
+
+#[derive(Resource)]
+struct MeleeAssets {
+    mesh: Handle<Mesh>,
+    swing_material: Handle<StandardMaterial>,
+    parry_material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = MeleeAssets {
+        mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        swing_material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(10.0, 10.0, 10.0),
+            ..Default::default()
+        }),
+        parry_material: materials.add(StandardMaterial {
+            emissive: LinearRgba::rgb(2.0, 8.0, 20.0),
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+#[derive(Component)]
+struct SwingBlade;
+
+/// Where the blade is, relative to the swinger.
+fn blade_transform(swing: &Swing, ability_offset: &AbilityOffset) -> Transform {
+    // Sweep from one side of the arc to the other.
+    let angle = swing.arc * (0.5 - swing.fraction());
+    let rotation = Quat::from_rotation_y(angle);
+    let translation = rotation * FORWARD * (PLAYER_R + swing.range * 0.5) + ability_offset.to_vec();
+    Transform {
+        translation,
+        rotation,
+        scale: Vec3::new(BLADE_WIDTH, BLADE_WIDTH, swing.range),
+    }
+}
+
+fn draw_swing_system(
+    mut commands: Commands,
+    assets: Res<MeleeAssets>,
+    query: Query<(Entity, &Swing, &AbilityOffset), Added<Swing>>,
+) {
+    for (entity, swing, ability_offset) in &query {
+        let material = if swing.parry {
+            assets.parry_material.clone_weak()
+        } else {
+            assets.swing_material.clone_weak()
+        };
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                MeshMaterial3d::from(material),
+                Mesh3d::from(assets.mesh.clone_weak()),
+                blade_transform(swing, ability_offset),
+                SwingBlade,
+            ));
+        });
+    }
+}
+
+fn update_swing_system(
+    swing_q: Query<(&Swing, &AbilityOffset, &Children)>,
+    mut blade_q: Query<&mut Transform, With<SwingBlade>>,
+) {
+    for (swing, ability_offset, children) in &swing_q {
+        for &child in children {
+            if let Ok(mut transform) = blade_q.get_mut(child) {
+                *transform = blade_transform(swing, ability_offset);
+            }
+        }
+    }
+}
+
+fn remove_swing_system(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Swing>,
+    children_q: Query<&Children>,
+    blade_q: Query<(), With<SwingBlade>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children_q.get(entity) else {
+            continue;
+        };
+        for &child in children {
+            if blade_q.get(child).is_ok() {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+}
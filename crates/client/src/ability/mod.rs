@@ -1,15 +1,35 @@
 use bevy::app::Plugin;
 use bevy::prelude::Component;
+use capacitor_field::CapacitorFieldPlugin;
+use cone::ConePlugin;
+use dilation_field::DilationFieldPlugin;
+use energy_beam::EnergyBeamPlugin;
 use gravity_ball::GravityBallPlugin;
 use grenade::GrenadePlugin;
 use gun::GunPlugin;
+use head::HeadPlugin;
+use heal_beam::HealBeamPlugin;
+use knockback::KnockbackPlugin;
+use laser::LaserPlugin;
+use melee::MeleePlugin;
 use rocket::RocketPlugin;
+use shield::ShieldPlugin;
 use transport::TransportBeamPlugin;
 
+mod capacitor_field;
+mod cone;
+mod dilation_field;
+mod energy_beam;
 mod gravity_ball;
 pub mod grenade;
 mod gun;
+mod head;
+mod heal_beam;
+mod knockback;
+mod laser;
+mod melee;
 pub mod rocket;
+mod shield;
 mod transport;
 
 #[derive(Component)]
@@ -19,10 +39,20 @@ pub struct AbilityPlugin;
 impl Plugin for AbilityPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_plugins((
+            CapacitorFieldPlugin,
+            ConePlugin,
+            DilationFieldPlugin,
+            EnergyBeamPlugin,
             GravityBallPlugin,
             GrenadePlugin,
             GunPlugin,
+            HeadPlugin,
+            HealBeamPlugin,
+            KnockbackPlugin,
+            LaserPlugin,
+            MeleePlugin,
             RocketPlugin,
+            ShieldPlugin,
             TransportBeamPlugin,
         ));
     }
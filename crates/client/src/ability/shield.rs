@@ -0,0 +1,127 @@
+use bevy::app::Plugin;
+use bevy::app::Startup;
+use bevy::app::Update;
+use bevy::color::Color;
+use bevy::color::LinearRgba;
+use bevy::pbr::MeshMaterial3d;
+use bevy::pbr::NotShadowCaster;
+use bevy::prelude::Added;
+use bevy::prelude::AlphaMode;
+use bevy::prelude::Assets;
+use bevy::prelude::BuildChildren;
+use bevy::prelude::ChildBuild;
+use bevy::prelude::Children;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::Cuboid;
+use bevy::prelude::DespawnRecursiveExt;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::Mesh;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::RemovedComponents;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::Sphere;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Transform;
+use bevy::prelude::With;
+use bevy::prelude::World;
+use engine::ability::shield::Barrier;
+use engine::ability::shield::PlantedShield;
+use engine::PLAYER_HEIGHT;
+
+pub struct ShieldPlugin;
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.add_systems(Startup, setup).add_systems(
+            Update,
+            (
+                draw_barrier_system,
+                remove_barrier_system,
+                draw_planted_shield_system,
+            ),
+        );
+    }
+}
+
+#[derive(Resource)]
+struct ShieldAssets {
+    barrier_mesh: Handle<Mesh>,
+    planted_mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+fn setup(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let assets = ShieldAssets {
+        barrier_mesh: meshes.add(Sphere::new(PLAYER_HEIGHT * 0.6)),
+        planted_mesh: meshes.add(Cuboid::new(1.0, 1.0, 1.0)),
+        material: materials.add(StandardMaterial {
+            base_color: Color::linear_rgba(0.4, 0.7, 1.0, 0.25),
+            emissive: LinearRgba::rgb(0.2, 0.5, 1.0),
+            alpha_mode: AlphaMode::Blend,
+            ..Default::default()
+        }),
+    };
+
+    commands.queue(|world: &mut World| world.insert_resource(assets));
+}
+
+#[derive(Component)]
+struct BarrierBubble;
+
+fn draw_barrier_system(
+    mut commands: Commands,
+    assets: Res<ShieldAssets>,
+    query: Query<Entity, Added<Barrier>>,
+) {
+    for entity in &query {
+        commands.entity(entity).with_children(|builder| {
+            builder.spawn((
+                MeshMaterial3d::from(assets.material.clone_weak()),
+                Mesh3d::from(assets.barrier_mesh.clone_weak()),
+                Transform::default(),
+                NotShadowCaster,
+                BarrierBubble,
+            ));
+        });
+    }
+}
+
+fn remove_barrier_system(
+    mut commands: Commands,
+    mut removed: RemovedComponents<Barrier>,
+    children_q: Query<&Children>,
+    bubble_q: Query<(), With<BarrierBubble>>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = children_q.get(entity) else {
+            continue;
+        };
+        for &child in children {
+            if bubble_q.get(child).is_ok() {
+                commands.entity(child).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn draw_planted_shield_system(
+    mut commands: Commands,
+    assets: Res<ShieldAssets>,
+    query: Query<Entity, Added<PlantedShield>>,
+) {
+    for entity in &query {
+        // The shield's scale is its size, so a unit cube fits it.
+        commands.entity(entity).insert((
+            MeshMaterial3d::from(assets.material.clone_weak()),
+            Mesh3d::from(assets.planted_mesh.clone_weak()),
+        ));
+    }
+}
@@ -1,6 +1,7 @@
 use bevy::color::palettes::css::BLACK;
 use bevy::color::palettes::css::GREEN;
 use bevy::math::primitives::Rectangle;
+use bevy::prelude::AlphaMode;
 use bevy::prelude::Color;
 use bevy::prelude::Handle;
 use bevy::prelude::Mesh;
@@ -57,4 +58,26 @@ impl BarAssets {
             bg_material: builder.materials.add(bg),
         }
     }
+
+    pub fn shieldbar(builder: &mut Builder) -> Self {
+        let fg = StandardMaterial {
+            base_color: Color::linear_rgb(0.6, 0.8, 1.0),
+            unlit: true,
+            depth_bias: 1000.0,
+            ..Default::default()
+        };
+        // The shield bar is only visible when there's a shield.
+        let bg = StandardMaterial {
+            base_color: Color::NONE,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            depth_bias: -1000.0,
+            ..Default::default()
+        };
+        BarAssets {
+            mesh: builder.meshes.add(Rectangle::new(1.0, 1.0)),
+            fg_material: builder.materials.add(fg),
+            bg_material: builder.materials.add(bg),
+        }
+    }
 }
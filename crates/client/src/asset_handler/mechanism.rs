@@ -0,0 +1,39 @@
+use bevy::color::palettes::css::DARK_SLATE_GRAY;
+use bevy::color::palettes::css::SADDLE_BROWN;
+use bevy::color::palettes::css::SLATE_GRAY;
+use bevy::color::LinearRgba;
+use bevy::prelude::Color;
+use bevy::prelude::Handle;
+use bevy::prelude::StandardMaterial;
+
+use super::Builder;
+
+pub struct MechanismAssets {
+    pub door: Handle<StandardMaterial>,
+    pub platform: Handle<StandardMaterial>,
+    pub elevator: Handle<StandardMaterial>,
+    pub off: Handle<StandardMaterial>,
+    pub on: Handle<StandardMaterial>,
+}
+
+impl MechanismAssets {
+    pub fn new(builder: &mut Builder) -> Self {
+        let door_color: Color = SADDLE_BROWN.into();
+        let platform_color: Color = SLATE_GRAY.into();
+        let elevator_color: Color = DARK_SLATE_GRAY.into();
+
+        MechanismAssets {
+            door: builder.materials.add(door_color),
+            platform: builder.materials.add(platform_color),
+            elevator: builder.materials.add(elevator_color),
+            off: builder.materials.add(StandardMaterial {
+                emissive: LinearRgba::rgb(2.0, 0.0, 0.0),
+                ..Default::default()
+            }),
+            on: builder.materials.add(StandardMaterial {
+                emissive: LinearRgba::rgb(0.0, 4.0, 0.0),
+                ..Default::default()
+            }),
+        }
+    }
+}
@@ -10,6 +10,7 @@ use bevy::prelude::Resource;
 use bevy::prelude::StandardMaterial;
 use bevy_hanabi::EffectAsset;
 use character::CharacterAssets;
+use mechanism::MechanismAssets;
 use music::load_music;
 use target::TargetAssets;
 use temperature::TemperatureAssets;
@@ -18,6 +19,7 @@ use wall::WallAssets;
 
 pub mod bar;
 pub mod character;
+pub mod mechanism;
 pub mod music;
 pub mod target;
 pub mod temperature;
@@ -36,6 +38,7 @@ pub struct Builder<'a> {
 pub struct AssetHandler {
     pub healthbar: BarAssets,
     pub energybar: BarAssets,
+    pub shieldbar: BarAssets,
     pub time_dilation: TimeDilationAssets,
     pub player: CharacterAssets,
     pub ally: CharacterAssets,
@@ -43,6 +46,7 @@ pub struct AssetHandler {
     pub music: Handle<LoadedFolder>,
     pub target: TargetAssets,
     pub wall: WallAssets,
+    pub mechanism: MechanismAssets,
     pub temperature: TemperatureAssets,
 }
 
@@ -52,12 +56,14 @@ impl Builder<'_> {
             music: load_music(self),
             healthbar: BarAssets::healthbar(self),
             energybar: BarAssets::energybar(self),
+            shieldbar: BarAssets::shieldbar(self),
             time_dilation: TimeDilationAssets::new(self),
             player: CharacterAssets::player(self),
             ally: CharacterAssets::ally(self),
             enemy: CharacterAssets::enemy(self),
             target: TargetAssets::new(self),
             wall: WallAssets::new(self),
+            mechanism: MechanismAssets::new(self),
             temperature: TemperatureAssets::new(self),
         }
     }
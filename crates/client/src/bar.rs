@@ -65,19 +65,40 @@ impl<T> Default for BarChildMarker<T> {
     }
 }
 
-pub trait HasBar: fmt::Debug {
-    fn percent(&self) -> f32;
+/// Something we can draw a bar for.
+///
+/// Usually this is the component the bar shows, but it can be a marker for a
+/// different view of one.
+pub trait HasBar: Send + Sync + 'static {
+    type Source: Component + fmt::Debug;
+
+    fn percent(source: &Self::Source) -> f32;
 }
 
 impl HasBar for Health {
-    fn percent(&self) -> f32 {
-        (self.cur / self.max).max(0.0)
+    type Source = Health;
+
+    fn percent(health: &Health) -> f32 {
+        (health.cur / health.max).max(0.0)
     }
 }
 
 impl HasBar for Energy {
-    fn percent(&self) -> f32 {
-        (self.cur / self.max).max(0.0)
+    type Source = Energy;
+
+    fn percent(energy: &Energy) -> f32 {
+        (energy.cur / energy.max).max(0.0)
+    }
+}
+
+/// A character's shield, relative to their max health.
+pub struct Shield;
+
+impl HasBar for Shield {
+    type Source = Health;
+
+    fn percent(health: &Health) -> f32 {
+        (health.shield / health.max).clamp(0.0, 1.0)
     }
 }
 
@@ -110,6 +131,12 @@ impl Default for Bar<Energy> {
     }
 }
 
+impl Default for Bar<Shield> {
+    fn default() -> Self {
+        Self::new(0.26, Vec2::new(0.45, 0.04))
+    }
+}
+
 pub struct BarPlugin;
 
 impl Plugin for BarPlugin {
@@ -119,6 +146,7 @@ impl Plugin for BarPlugin {
             (
                 (bar_add_system::<Health>, bar_update_system::<Health>).chain(),
                 (bar_add_system::<Energy>, bar_update_system::<Energy>).chain(),
+                (bar_add_system::<Shield>, bar_update_system::<Shield>).chain(),
             ),
         );
     }
@@ -166,14 +194,30 @@ impl BarAssets for Energy {
     }
 }
 
+impl BarAssets for Shield {
+    fn assets(
+        assets: &AssetHandler,
+    ) -> (
+        Handle<StandardMaterial>,
+        Handle<StandardMaterial>,
+        Handle<Mesh>,
+    ) {
+        (
+            assets.shieldbar.fg_material.clone(),
+            assets.shieldbar.bg_material.clone(),
+            assets.shieldbar.mesh.clone(),
+        )
+    }
+}
+
 #[derive(QueryData)]
-struct ParentQuery<T: Component> {
+struct ParentQuery<T: Send + Sync + 'static> {
     entity: Entity,
     global_transform: &'static GlobalTransform,
     bar: &'static Bar<T>,
 }
 
-fn bar_add_system<T: Component + BarAssets + Default>(
+fn bar_add_system<T: HasBar + BarAssets>(
     mut commands: Commands,
     assets: Res<AssetHandler>,
     parents: Query<ParentQuery<T>, Added<Bar<T>>>,
@@ -218,8 +262,8 @@ fn bar_add_system<T: Component + BarAssets + Default>(
 // That has a child, with our transform; bar_q.
 // That has children; the first is the foreground bar, the second is the
 // background.
-pub fn bar_update_system<T: Component + HasBar>(
-    entity_q: Query<(&Transform, &T), (Without<BarMarker<T>>, Without<BarChildMarker<T>>)>,
+pub fn bar_update_system<T: HasBar>(
+    entity_q: Query<(&Transform, &T::Source), (Without<BarMarker<T>>, Without<BarChildMarker<T>>)>,
     graphics_q: Query<
         (&Parent, &Transform, &Bar<T>),
         (Without<BarMarker<T>>, Without<BarChildMarker<T>>),
@@ -250,7 +294,7 @@ pub fn bar_update_system<T: Component + HasBar>(
             );
             continue;
         };
-        let percent = quantity.percent();
+        let percent = T::percent(quantity);
         let rotation = graphics_transform.rotation.inverse() * entity_transform.rotation.inverse();
         let scale = (graphics_transform.scale * entity_transform.scale).recip();
         transform.rotation = rotation;
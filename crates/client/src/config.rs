@@ -264,7 +264,8 @@ impl Default for PlayerConfig {
                 left_shoulder: "frag_grenade".into(),
                 right_shoulder: "gravity_ball".into(),
                 legs: "transport_beam".into(),
-                head: "noop".into(),
+                head: "targeting_computer".into(),
+                body: "capacitor".into(),
             },
         }
     }
@@ -35,6 +35,7 @@ use engine::Player;
 use crate::aim::BlocksSight;
 use crate::asset_handler::AssetHandler;
 use crate::bar::Bar;
+use crate::bar::Shield;
 use crate::in_plane;
 use crate::Config;
 
@@ -149,6 +150,7 @@ fn draw_player_system(
                     BlocksSight,
                     Bar::<Health>::default(),
                     Bar::<Energy>::default(),
+                    Bar::<Shield>::default(),
                 ));
             });
     }
@@ -181,6 +183,7 @@ fn draw_enemy_system(
                     BlocksSight,
                     Bar::<Health>::default(),
                     Bar::<Energy>::default(),
+                    Bar::<Shield>::default(),
                 ));
             });
     }
@@ -213,6 +216,7 @@ fn draw_ally_system(
                     BlocksSight,
                     Bar::<Health>::default(),
                     Bar::<Energy>::default(),
+                    Bar::<Shield>::default(),
                 ));
             });
     }
@@ -0,0 +1,126 @@
+use bevy::pbr::MeshMaterial3d;
+use bevy::prelude::Added;
+use bevy::prelude::BuildChildren;
+use bevy::prelude::Changed;
+use bevy::prelude::Children;
+use bevy::prelude::Commands;
+use bevy::prelude::Component;
+use bevy::prelude::Entity;
+use bevy::prelude::Handle;
+use bevy::prelude::InheritedVisibility;
+use bevy::prelude::Mesh3d;
+use bevy::prelude::Query;
+use bevy::prelude::Res;
+use bevy::prelude::StandardMaterial;
+use bevy::prelude::Transform;
+use bevy::prelude::Vec3;
+use bevy::prelude::With;
+use engine::level::mechanism::Mover;
+use engine::level::mechanism::MoverKind;
+use engine::level::mechanism::PressurePlate;
+use engine::level::mechanism::Switch;
+use engine::level::LevelGrid;
+use engine::level::SHORT_WALL;
+
+use crate::asset_handler::AssetHandler;
+
+const PLATE_HEIGHT: f32 = 0.02;
+
+/// A mesh showing whether a switch or pressure plate is on.
+#[derive(Component)]
+pub struct Indicator;
+
+fn add_cuboid(
+    commands: &mut Commands,
+    assets: &AssetHandler,
+    entity: Entity,
+    dim: Vec3,
+    material: Handle<StandardMaterial>,
+) -> Entity {
+    // Add InheritedVisibility to make bevy happy.
+    commands
+        .entity(entity)
+        .insert(InheritedVisibility::default());
+    let child = commands
+        .spawn((
+            Mesh3d(assets.wall.shape.clone_weak()),
+            MeshMaterial3d(material),
+            Transform::from_scale(dim),
+        ))
+        .id();
+    commands.entity(entity).add_child(child);
+    child
+}
+
+pub fn draw_mover_system(
+    mut commands: Commands,
+    assets: Res<AssetHandler>,
+    query: Query<(Entity, &Mover), Added<Mover>>,
+) {
+    for (entity, mover) in &query {
+        let material = match mover.kind {
+            MoverKind::Door => assets.mechanism.door.clone(),
+            MoverKind::Elevator => assets.mechanism.elevator.clone(),
+            MoverKind::Platform => assets.mechanism.platform.clone(),
+        };
+        add_cuboid(&mut commands, &assets, entity, mover.dim, material);
+    }
+}
+
+pub fn draw_plate_system(
+    mut commands: Commands,
+    assets: Res<AssetHandler>,
+    query: Query<Entity, Added<PressurePlate>>,
+) {
+    let dim = Vec3::new(LevelGrid::PIXEL * 0.8, PLATE_HEIGHT, LevelGrid::PIXEL * 0.8);
+    for entity in &query {
+        let material = assets.mechanism.off.clone();
+        let plate = add_cuboid(&mut commands, &assets, entity, dim, material);
+        commands.entity(plate).insert(Indicator);
+    }
+}
+
+pub fn draw_switch_system(
+    mut commands: Commands,
+    assets: Res<AssetHandler>,
+    query: Query<Entity, Added<Switch>>,
+) {
+    let dim = Vec3::new(LevelGrid::PIXEL, SHORT_WALL, LevelGrid::PIXEL);
+    for entity in &query {
+        let material = assets.mechanism.off.clone();
+        let switch = add_cuboid(&mut commands, &assets, entity, dim, material);
+        commands.entity(switch).insert(Indicator);
+    }
+}
+
+fn set_indicator(
+    on: bool,
+    children: &Children,
+    assets: &AssetHandler,
+    indicator_q: &mut Query<&mut MeshMaterial3d<StandardMaterial>, With<Indicator>>,
+) {
+    let material = if on {
+        assets.mechanism.on.clone()
+    } else {
+        assets.mechanism.off.clone()
+    };
+    for &child in children {
+        if let Ok(mut mesh_material) = indicator_q.get_mut(child) {
+            *mesh_material = material.clone().into();
+        }
+    }
+}
+
+pub fn update_indicator_system(
+    assets: Res<AssetHandler>,
+    plate_q: Query<(&PressurePlate, &Children), Changed<PressurePlate>>,
+    switch_q: Query<(&Switch, &Children), Changed<Switch>>,
+    mut indicator_q: Query<&mut MeshMaterial3d<StandardMaterial>, With<Indicator>>,
+) {
+    for (plate, children) in &plate_q {
+        set_indicator(plate.pressed, children, &assets, &mut indicator_q);
+    }
+    for (switch, children) in &switch_q {
+        set_indicator(switch.on, children, &assets, &mut indicator_q);
+    }
+}
@@ -7,6 +7,7 @@ mod character;
 mod death;
 pub mod explosion;
 mod level;
+mod mechanism;
 mod temperature;
 mod time_dilation;
 
@@ -38,6 +39,12 @@ impl Plugin for DrawPlugin {
                     level::update_wall_system,
                     level::draw_lights_system,
                 ),
+                (
+                    mechanism::draw_mover_system,
+                    mechanism::draw_plate_system,
+                    mechanism::draw_switch_system,
+                    mechanism::update_indicator_system,
+                ),
             ),
         )
         .add_plugins((CharacterPlugin, ExplosionPlugin));
@@ -0,0 +1,349 @@
+use std::path::PathBuf;
+
+use bevy::color::palettes::css::GOLD;
+use bevy::color::palettes::css::ORANGE_RED;
+use bevy::color::palettes::css::ROYAL_BLUE;
+use bevy::color::palettes::css::SLATE_GRAY;
+use bevy::color::palettes::css::TEAL;
+use bevy::input::ButtonInput;
+use bevy::math::Isometry3d;
+use bevy::prelude::in_state;
+use bevy::prelude::App;
+use bevy::prelude::Camera;
+use bevy::prelude::Commands;
+use bevy::prelude::Entity;
+use bevy::prelude::Gizmos;
+use bevy::prelude::GlobalTransform;
+use bevy::prelude::IntoSystemConfigs;
+use bevy::prelude::KeyCode;
+use bevy::prelude::MouseButton;
+use bevy::prelude::NextState;
+use bevy::prelude::Plugin;
+use bevy::prelude::Query;
+use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
+use bevy::prelude::State;
+use bevy::prelude::Transform;
+use bevy::prelude::Update;
+use bevy::prelude::Vec2;
+use bevy::prelude::Vec3;
+use bevy::prelude::With;
+use bevy::state::app::AppExtStates;
+use bevy::time::Time;
+use bevy::window::PrimaryWindow;
+use bevy::window::Window;
+use bevy_egui::egui;
+use bevy_egui::egui::Slider;
+use bevy_egui::EguiContexts;
+use engine::level::despawn_level;
+use engine::level::mechanism::OBJECTIVE_CHANNEL;
+use engine::level::Cell;
+use engine::level::InLevel;
+use engine::level::LevelGrid;
+use engine::level::LevelProps;
+use engine::level::LevelSource;
+use engine::run::Run;
+use engine::run::Stage;
+use engine::run::StageGoal;
+use engine::AppState;
+use engine::NumAi;
+use engine::UP;
+use engine::UP_PLANE;
+use oxidized_navigation::NavMesh;
+
+use crate::in_plane;
+use crate::t;
+
+/// The size of a new level, in cells.
+const NEW_LEVEL_SIZE: u32 = 50;
+const CAMERA_SPEED: f32 = 8.0;
+
+/// A plugin for editing levels in place.
+///
+/// The level is loaded from, and saved to, `path`.
+pub struct EditorPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        let grid = LevelGrid::load(&self.path).unwrap_or_else(|err| {
+            tracing::warn!(?err, path = ?self.path, "Could not load level; starting a new one");
+            LevelGrid::new(NEW_LEVEL_SIZE, NEW_LEVEL_SIZE, Cell::Floor)
+        });
+
+        app.insert_state(AppState::Editor)
+            .insert_resource(Run::new(vec![Stage {
+                level: LevelSource::Grid(grid.clone()),
+                goal: StageGoal::Endless,
+            }]))
+            .insert_resource(Editor {
+                path: self.path.clone(),
+                grid,
+                brush: Cell::Wall,
+                radius: 0,
+                channel: 0,
+                dirty: false,
+                status: String::new(),
+            })
+            .add_systems(
+                Update,
+                (
+                    editor_ui,
+                    (
+                        paint_system,
+                        rebuild_system,
+                        camera_system,
+                        draw_grid_system,
+                        draw_nav_mesh_system,
+                    )
+                        .run_if(in_state(AppState::Editor)),
+                ),
+            );
+    }
+}
+
+#[derive(Resource)]
+struct Editor {
+    path: PathBuf,
+    grid: LevelGrid,
+    brush: Cell,
+    radius: u32,
+    /// The channel for mechanism brushes.
+    channel: u8,
+    /// Whether the grid has changed since we last spawned it.
+    dirty: bool,
+    status: String,
+}
+
+impl Editor {
+    fn paint(&mut self, x: u32, z: u32) {
+        // Spawn markers are placed one at a time.
+        let radius = match self.brush {
+            Cell::PlayerSpawn | Cell::EnemySpawn | Cell::Platform(_) | Cell::PlatformEnd(_) => 0,
+            _ => self.radius as i64,
+        };
+
+        for dx in -radius..=radius {
+            for dz in -radius..=radius {
+                if dx * dx + dz * dz > radius * radius {
+                    continue;
+                }
+                let (x, z) = (x as i64 + dx, z as i64 + dz);
+                if x < 0 || z < 0 || x >= self.grid.width() as i64 || z >= self.grid.height() as i64
+                {
+                    continue;
+                }
+                let (x, z) = (x as u32, z as u32);
+                if self.grid.get(x, z) != self.brush {
+                    self.grid.set(x, z, self.brush);
+                    self.dirty = true;
+                }
+            }
+        }
+    }
+}
+
+fn editor_ui(
+    mut contexts: EguiContexts,
+    mut editor: ResMut<Editor>,
+    state: Res<State<AppState>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    egui::Window::new(t!("editor")).show(contexts.ctx_mut(), |ui| {
+        if state.get() != &AppState::Editor {
+            if ui.button(t!("stop_play_test")).clicked() {
+                // Respawn the level, to get rid of everyone in it.
+                editor.dirty = true;
+                next_state.set(AppState::Editor);
+            }
+            return;
+        }
+
+        let channel = editor.channel;
+        let brushes = [
+            (Cell::Floor, t!("brush_floor")),
+            (Cell::Pit, t!("brush_pit")),
+            (Cell::ShortWall, t!("brush_short_wall")),
+            (Cell::Wall, t!("brush_wall")),
+            (Cell::PlayerSpawn, t!("brush_player_spawn")),
+            (Cell::EnemySpawn, t!("brush_enemy_spawn")),
+            (Cell::Door(channel), t!("brush_door")),
+            (Cell::PressurePlate(channel), t!("brush_pressure_plate")),
+            (Cell::Switch(channel), t!("brush_switch")),
+            (Cell::Platform(channel), t!("brush_platform")),
+            (Cell::PlatformEnd(channel), t!("brush_platform_end")),
+            (Cell::ShortElevator(channel), t!("brush_short_elevator")),
+            (Cell::Elevator(channel), t!("brush_elevator")),
+        ];
+        for (cell, label) in brushes {
+            ui.selectable_value(&mut editor.brush, cell, label);
+        }
+        ui.add(Slider::new(&mut editor.radius, 0..=5).text(t!("brush_size")));
+        if ui
+            .add(Slider::new(&mut editor.channel, 0..=OBJECTIVE_CHANNEL).text(t!("channel")))
+            .changed()
+        {
+            editor.brush = with_channel(editor.brush, editor.channel);
+        }
+
+        ui.separator();
+        if ui.button(t!("play_test")).clicked() {
+            next_state.set(AppState::Running);
+        }
+        if ui.button(t!("save")).clicked() {
+            editor.status = match editor.grid.save(&editor.path) {
+                Ok(()) => t!("saved", path = editor.path.display().to_string()),
+                Err(err) => t!("save_failed", error = err.to_string()),
+            };
+        }
+        ui.label(editor.status.as_str());
+    });
+}
+
+/// The same kind of cell, but on a different channel, if it has one.
+fn with_channel(cell: Cell, channel: u8) -> Cell {
+    match cell {
+        Cell::Door(_) => Cell::Door(channel),
+        Cell::PressurePlate(_) => Cell::PressurePlate(channel),
+        Cell::Switch(_) => Cell::Switch(channel),
+        Cell::Platform(_) => Cell::Platform(channel),
+        Cell::PlatformEnd(_) => Cell::PlatformEnd(channel),
+        Cell::ShortElevator(_) => Cell::ShortElevator(channel),
+        Cell::Elevator(_) => Cell::Elevator(channel),
+        cell => cell,
+    }
+}
+
+fn cursor_location(
+    window_q: &Query<&Window, With<PrimaryWindow>>,
+    camera_q: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec3> {
+    let cursor = window_q.get_single().ok()?.cursor_position()?;
+    let (camera, camera_gt) = camera_q.get_single().ok()?;
+    let ray = camera.viewport_to_world(camera_gt, cursor).ok()?;
+    let distance = ray.intersect_plane(Vec3::ZERO, UP_PLANE)?;
+    Some(ray.get_point(distance))
+}
+
+fn paint_system(
+    mut contexts: EguiContexts,
+    mut editor: ResMut<Editor>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform)>,
+) {
+    if !mouse.pressed(MouseButton::Left) || contexts.ctx_mut().wants_pointer_input() {
+        return;
+    }
+    let Some(loc) = cursor_location(&window_q, &camera_q) else {
+        return;
+    };
+    if let Some((x, z)) = editor.grid.cell_at(loc) {
+        editor.paint(x, z);
+    }
+}
+
+/// Respawn the level after a brush stroke, so the level geometry and navmesh
+/// catch up with the grid.
+fn rebuild_system(
+    mut commands: Commands,
+    mut editor: ResMut<Editor>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    mut props: ResMut<LevelProps>,
+    mut num_ai: ResMut<NumAi>,
+    level_q: Query<Entity, With<InLevel>>,
+) {
+    if !editor.dirty || mouse.pressed(MouseButton::Left) {
+        return;
+    }
+    editor.dirty = false;
+
+    despawn_level(&mut commands, &level_q);
+    num_ai.enemies = 0;
+    editor.grid.spawn(&mut commands, &mut props);
+}
+
+fn camera_system(
+    keys: Res<ButtonInput<KeyCode>>,
+    time: Res<Time>,
+    mut camera_q: Query<&mut Transform, With<Camera>>,
+) {
+    let mut dir = Vec2::ZERO;
+    if keys.any_pressed([KeyCode::KeyW, KeyCode::ArrowUp]) {
+        dir.y += 1.0;
+    }
+    if keys.any_pressed([KeyCode::KeyS, KeyCode::ArrowDown]) {
+        dir.y -= 1.0;
+    }
+    if keys.any_pressed([KeyCode::KeyA, KeyCode::ArrowLeft]) {
+        dir.x -= 1.0;
+    }
+    if keys.any_pressed([KeyCode::KeyD, KeyCode::ArrowRight]) {
+        dir.x += 1.0;
+    }
+
+    let delta =
+        Vec3::new(dir.x, 0.0, -dir.y).normalize_or_zero() * CAMERA_SPEED * time.delta_secs();
+    for mut transform in &mut camera_q {
+        transform.translation += delta;
+    }
+}
+
+fn draw_grid_system(
+    mut contexts: EguiContexts,
+    editor: Res<Editor>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform)>,
+    mut gizmos: Gizmos,
+) {
+    let rotation = in_plane().rotation;
+    let marker_y = 0.02 * UP;
+
+    // Spawn markers look just like floor, and platform ends like pits, so we
+    // need to draw them.
+    for (x, z, cell) in editor.grid.cells() {
+        let color = match cell {
+            Cell::PlayerSpawn => ROYAL_BLUE,
+            Cell::EnemySpawn => ORANGE_RED,
+            Cell::PlatformEnd(_) => SLATE_GRAY,
+            _ => continue,
+        };
+        let loc = editor.grid.cell_center(x, z) + marker_y;
+        gizmos.circle(
+            Isometry3d::new(loc, rotation),
+            LevelGrid::PIXEL * 0.4,
+            color,
+        );
+    }
+
+    if contexts.ctx_mut().wants_pointer_input() {
+        return;
+    }
+    let Some((x, z)) = cursor_location(&window_q, &camera_q).and_then(|l| editor.grid.cell_at(l))
+    else {
+        return;
+    };
+    let loc = editor.grid.cell_center(x, z) + marker_y;
+    let size = (editor.radius * 2 + 1) as f32 * LevelGrid::PIXEL;
+    gizmos.rect(Isometry3d::new(loc, rotation), Vec2::splat(size), GOLD);
+}
+
+fn draw_nav_mesh_system(nav_mesh: Res<NavMesh>, mut gizmos: Gizmos) {
+    let nav_mesh = nav_mesh.get();
+    let Ok(tiles) = nav_mesh.read() else {
+        return;
+    };
+
+    for tile in tiles.get_tiles().values() {
+        for polygon in &tile.polygons {
+            let points = polygon
+                .indices
+                .iter()
+                .chain(polygon.indices.first())
+                .map(|i| tile.vertices[*i as usize] + 0.05 * UP);
+            gizmos.linestrip(points, TEAL);
+        }
+    }
+}
@@ -41,6 +41,7 @@ mod config;
 mod controls;
 pub mod debug;
 mod draw;
+pub mod editor;
 mod i18n;
 mod particles;
 mod shapes;
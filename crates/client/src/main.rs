@@ -1,3 +1,5 @@
+use std::path::PathBuf;
+
 use bevy::math::bool;
 use bevy::prelude::App;
 use bevy::prelude::Commands;
@@ -6,8 +8,11 @@ use bevy::prelude::Res;
 use bevy::prelude::Startup;
 use clap::Parser;
 use client::debug::DebugTextPlugin;
+use client::editor::EditorPlugin;
 use client::Config;
+use engine::ability::props::PropsHotReloadPlugin;
 use engine::player::PlayerInfo;
+use engine::run::Run;
 use engine::Player;
 
 #[derive(Parser)]
@@ -33,6 +38,15 @@ struct Args {
     /// Whether to show debug text
     #[arg(long)]
     debug_text: bool,
+    /// Whether to reload ability props when their file changes
+    #[arg(long)]
+    hot_reload_props: bool,
+    /// Play a multi-stage run, with levels generated from this seed
+    #[arg(long)]
+    run: Option<u64>,
+    /// Edit the level at this path, instead of playing
+    #[arg(long)]
+    editor: Option<PathBuf>,
 }
 
 fn main() {
@@ -63,6 +77,13 @@ fn main() {
     ))
     .add_systems(Startup, player_spawner);
 
+    if let Some(seed) = args.run {
+        app.insert_resource(Run::standard(seed));
+    }
+    if let Some(path) = &args.editor {
+        app.add_plugins(EditorPlugin { path: path.clone() });
+    }
+
     debug_stuff(&mut app, &args);
 
     app.run();
@@ -91,6 +112,9 @@ fn debug_stuff(app: &mut App, args: &Args) {
     if args.all || args.debug_text {
         app.add_plugins(DebugTextPlugin);
     }
+    if args.all || args.hot_reload_props {
+        app.add_plugins(PropsHotReloadPlugin::default());
+    }
 }
 
 fn player_spawner(mut commands: Commands, config: Res<Config>) {
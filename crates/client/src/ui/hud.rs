@@ -22,8 +22,15 @@ use bevy::ui::FlexDirection;
 use bevy::ui::JustifyContent;
 use bevy::ui::Node;
 use bevy::ui::Val;
+use engine::ability::magazine::Magazine;
+use engine::ability::magazine::Magazines;
+use engine::ability::SideEnum;
+use engine::ability::Slot;
+use engine::run::Run;
+use engine::run::RunState;
 use engine::time::FrameCounter;
 use engine::NumAi;
+use engine::Player;
 
 use crate::t;
 
@@ -37,7 +44,14 @@ impl Plugin for HudPlugin {
             .add_systems(Startup, persistent_ui_setup)
             .add_systems(
                 Update,
-                (score_update, frame_time_update, fps_update, fps_track),
+                (
+                    score_update,
+                    stage_update,
+                    frame_time_update,
+                    fps_update,
+                    fps_track,
+                    ammo_update,
+                ),
             );
     }
 }
@@ -68,6 +82,12 @@ fn persistent_ui_setup(mut commands: Commands) {
                 TextColor::from(TEXT_COLOR),
                 Score,
             ));
+            parent.spawn((
+                Text::default(),
+                TextFont::from_font_size(40.0),
+                TextColor::from(TEXT_COLOR),
+                StageText,
+            ));
             parent.spawn((
                 Text(render_frame_time(Duration::ZERO)),
                 TextFont::from_font_size(40.0),
@@ -80,6 +100,12 @@ fn persistent_ui_setup(mut commands: Commands) {
                 TextColor::from(TEXT_COLOR),
                 FpsText,
             ));
+            parent.spawn((
+                Text::default(),
+                TextFont::from_font_size(40.0),
+                TextColor::from(TEXT_COLOR),
+                AmmoText,
+            ));
         });
 }
 
@@ -95,6 +121,18 @@ fn render_score(score: usize) -> String {
     t!("score", score = score)
 }
 
+#[derive(Component)]
+struct StageText;
+
+fn stage_update(run: Res<Run>, mut query: Query<&mut Text, With<StageText>>) {
+    let mut text = query.single_mut();
+    let stage = run.stage_index() + 1;
+    text.0 = match run.state() {
+        RunState::Won => t!("run_won"),
+        _ => t!("stage", stage = stage, stages = run.num_stages()),
+    };
+}
+
 #[derive(Component)]
 struct FrameTime;
 
@@ -152,3 +190,40 @@ fn render_fps(fps: f32) -> String {
     let fps = format!("{fps:0.1}");
     t!("fps", fps = fps)
 }
+
+#[derive(Component)]
+struct AmmoText;
+
+fn ammo_update(
+    player: Res<Player>,
+    player_q: Query<(&Player, &Magazines)>,
+    mut query: Query<&mut Text, With<AmmoText>>,
+) {
+    let mut text = query.single_mut();
+    let magazines = player_q
+        .iter()
+        .find(|(p, _)| **p == *player)
+        .map(|(_, magazines)| magazines);
+    text.0 = match magazines {
+        Some(magazines) => {
+            let render = |side| render_magazine(magazines.get(Slot::Arm(side)));
+            t!(
+                "ammo",
+                left = render(SideEnum::Left),
+                right = render(SideEnum::Right)
+            )
+        }
+        None => String::new(),
+    };
+}
+
+fn render_magazine(magazine: Option<&Magazine>) -> String {
+    match magazine {
+        None => "-".to_string(),
+        Some(magazine) if magazine.is_reloading() => t!("reloading"),
+        Some(magazine) => match magazine.reserve() {
+            Some(reserve) => format!("{}/{} ({reserve})", magazine.rounds(), magazine.size()),
+            None => format!("{}/{}", magazine.rounds(), magazine.size()),
+        },
+    }
+}
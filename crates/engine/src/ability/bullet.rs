@@ -1,5 +1,6 @@
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
+use bevy_ecs::event::EventWriter;
 use bevy_ecs::query::Added;
 use bevy_ecs::query::With;
 use bevy_ecs::query::Without;
@@ -15,21 +16,30 @@ use bevy_rapier3d::prelude::RigidBody;
 use bevy_rapier3d::prelude::Sensor;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
 
+use super::mobility::Airborne;
+use super::projectile::Projectile;
+use super::projectile::ProjectileKind;
+use super::props::Validate;
+use super::props::Validator;
 use crate::collision::TrackCollisionBundle;
 use crate::collision::TrackCollisions;
 use crate::level::InLevel;
 use crate::lifecycle::Lifetime;
+use crate::status_effect::Charge;
 use crate::status_effect::StatusProps;
 use crate::status_effect::Temperature;
 use crate::status_effect::TimeDilation;
 use crate::time::Dur;
+use crate::DamageEvent;
 use crate::Health;
 use crate::MassBundle;
 use crate::Object;
 use crate::Shootable;
 
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub struct BulletProps {
     pub radius: f32,
     pub mass: f32,
@@ -37,6 +47,21 @@ pub struct BulletProps {
     pub lifetime: Dur,
     pub damage: f32,
     pub heat: f32,
+    /// Electric charge given to whatever it hits; negative for negative.
+    #[serde(default)]
+    pub charge: f32,
+}
+
+impl Validate for BulletProps {
+    fn validate(&self, v: &mut Validator) {
+        v.positive("radius", self.radius);
+        v.positive("mass", self.mass);
+        v.positive("health", self.health);
+        v.duration("lifetime", self.lifetime);
+        v.finite("damage", self.damage);
+        v.finite("heat", self.heat);
+        v.finite("charge", self.charge);
+    }
 }
 
 pub struct BulletSpawner<G> {
@@ -52,6 +77,7 @@ pub struct Bullet {
     pub shooter: Entity,
     pub damage: f32,
     pub heat: f32,
+    pub charge: f32,
 }
 
 impl<G: Component> BulletSpawner<G> {
@@ -86,7 +112,14 @@ impl<G: Component> BulletSpawner<G> {
                 shooter: self.shooter,
                 damage: self.props.damage,
                 heat: self.props.heat,
+                charge: self.props.charge,
+            },
+            Projectile {
+                kind: ProjectileKind::Bullet,
+                shooter: self.shooter,
+                damage: self.props.damage,
             },
+            self.gun_kind,
         ));
     }
 }
@@ -117,19 +150,41 @@ pub fn collision_system(
         &Velocity,
         &TrackCollisions,
     )>,
-    mut health_q: Query<(&mut Health, &mut Temperature, &TimeDilation), Without<Bullet>>,
+    mut health_q: Query<
+        (&mut Health, &mut Temperature, &mut Charge, &TimeDilation),
+        Without<Bullet>,
+    >,
     mut momentum_q: Query<(&mut Velocity, &ReadMassProperties), Without<Bullet>>,
     shootable_q: Query<(), With<Shootable>>,
+    airborne_q: Query<(), With<Airborne>>,
+    projectile_q: Query<(), With<Projectile>>,
+    mut damage_events: EventWriter<DamageEvent>,
 ) {
     for (mut health, bullet, bullet_mass, bullet_velocity, colliding) in &mut bullet_q {
         let mut should_die = false;
         for &target in &colliding.targets {
+            // Bullets fly low enough that jumping clears them.
+            if airborne_q.contains(target) {
+                continue;
+            }
+            // Other projectiles are handled by the interaction matrix.
+            if projectile_q.contains(target) {
+                continue;
+            }
             if shootable_q.get(target).is_ok() {
                 should_die = true;
             }
-            if let Ok((mut health, mut temperature, dilation)) = health_q.get_mut(target) {
-                health.take(bullet.damage, dilation);
+            if let Ok((mut health, mut temperature, mut charge, dilation)) =
+                health_q.get_mut(target)
+            {
+                let amount = health.take(bullet.damage, dilation);
                 temperature.heat(bullet.heat);
+                charge.add(bullet.charge);
+                damage_events.send(DamageEvent {
+                    source: bullet.shooter,
+                    target,
+                    amount,
+                });
             }
 
             if let Ok((mut velocity, mass)) = momentum_q.get_mut(target) {
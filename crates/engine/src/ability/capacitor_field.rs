@@ -0,0 +1,175 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Quat;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::collision::sorted_intersections;
+use crate::level::InLevel;
+use crate::lifecycle::Lifetime;
+use crate::status_effect::Charge;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::GameSet;
+use crate::Health;
+use crate::Target;
+use crate::To2d;
+use crate::To3d;
+use crate::PLAYER_HEIGHT;
+use crate::SCHEDULE;
+
+pub struct CapacitorFieldPlugin;
+impl Plugin for CapacitorFieldPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins(AbilityDefPlugin::<CapacitorFieldAbility>::default())
+            .add_systems(SCHEDULE, field_system.in_set(GameSet::Stuff));
+    }
+}
+
+/// Plants a field at the user's target that charges up everything inside it.
+pub struct CapacitorFieldAbility;
+impl AbilityDef for CapacitorFieldAbility {
+    type Props = CapacitorFieldProps;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        AbilityId::from("capacitor_field")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct CapacitorFieldProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    /// How far away the field can be planted.
+    range: f32,
+    radius: f32,
+    lifetime: Dur,
+    /// Charge given to everything inside, each frame; negative for negative.
+    charge: f32,
+}
+
+impl Default for CapacitorFieldProps {
+    fn default() -> Self {
+        Self {
+            cost: 40.0,
+            cooldown: Dur::new(480),
+            gcd: Dur::new(30),
+            range: 5.0,
+            radius: 1.5,
+            lifetime: Dur::new(300),
+            charge: -0.02,
+        }
+    }
+}
+
+impl Validate for CapacitorFieldProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.non_negative("range", self.range);
+        v.positive("radius", self.radius);
+        v.positive_duration("lifetime", self.lifetime);
+        v.finite("charge", self.charge);
+    }
+}
+
+impl AbilityProps for CapacitorFieldProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A field that charges things, centered on the floor.
+#[derive(Component, Debug)]
+pub struct CapacitorField {
+    pub radius: f32,
+    /// Per frame; negative for negative.
+    pub charge: f32,
+}
+
+fn fire(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<(&Transform, &Target)>,
+    props: Res<CapacitorFieldProps>,
+) {
+    let Ok((transform, target)) = user_q.get(entity) else {
+        return;
+    };
+
+    let start = transform.translation.to_2d();
+    let center = start + (target.0 - start).clamp_length_max(props.range);
+
+    commands.spawn((
+        Transform::from_translation(center.to_3d(0.0)),
+        CapacitorField {
+            radius: props.radius,
+            charge: props.charge,
+        },
+        Lifetime::new(props.lifetime),
+        Health::new(1.0),
+        TimeDilation::default(),
+        InLevel,
+    ));
+}
+
+fn field_system(
+    rapier_context: ReadDefaultRapierContext,
+    field_q: Query<(&Transform, &CapacitorField)>,
+    mut target_q: Query<(&mut Charge, &TimeDilation)>,
+) {
+    for (transform, field) in &field_q {
+        // Tall enough to catch anything standing in it, or flying over it.
+        let shape = Collider::cylinder(PLAYER_HEIGHT, field.radius);
+        let targets = sorted_intersections(
+            &rapier_context,
+            transform.translation,
+            Quat::IDENTITY,
+            &shape,
+            QueryFilter::new(),
+        );
+
+        for target in targets {
+            if let Ok((mut charge, dilation)) = target_q.get_mut(target) {
+                charge.add(field.charge * dilation.factor());
+            }
+        }
+    }
+}
@@ -0,0 +1,288 @@
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::FRAC_PI_3;
+use std::f32::consts::TAU;
+use std::marker::PhantomData;
+
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::With;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Quat;
+use bevy_math::Vec3;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_rapier3d::prelude::RapierContext;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::linger::Linger;
+use super::linger::LingerPlugin;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::collision::sorted_intersections;
+use crate::level::Floor;
+use crate::status_effect::Temperature;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::AbilityOffset;
+use crate::To2d;
+use crate::To3d;
+use crate::FORWARD;
+use crate::PLAYER_HEIGHT;
+
+pub struct ConePlugin;
+impl Plugin for ConePlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<ConeAbility<Flamethrower>>::default(),
+            AbilityDefPlugin::<ConeAbility<Cryo>>::default(),
+            LingerPlugin::<ConeSpray>::default(),
+        ));
+    }
+}
+
+pub trait ConeKind: Send + Sync + Sized + 'static {
+    fn id() -> AbilityId;
+}
+
+pub struct Flamethrower;
+
+impl ConeKind for Flamethrower {
+    fn id() -> AbilityId {
+        AbilityId::from("flamethrower")
+    }
+}
+
+pub struct Cryo;
+
+impl ConeKind for Cryo {
+    fn id() -> AbilityId {
+        AbilityId::from("cryo")
+    }
+}
+
+/// Heats (or cools) everything in a cone in front of the user, for as long as
+/// it's held.
+pub struct ConeAbility<K: ConeKind> {
+    _marker: PhantomData<K>,
+}
+impl<K: ConeKind> AbilityDef for ConeAbility<K>
+where
+    ConeProps<K>: Default,
+{
+    type Props = ConeProps<K>;
+
+    const SLOTS: SlotKind = SlotKind::Arm;
+
+    fn id() -> AbilityId {
+        K::id()
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire::<K>)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct ConeProps<K: ConeKind> {
+    /// Energy per frame.
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    pub range: f32,
+    /// The full angle of the cone, in radians.
+    pub arc: f32,
+    /// Heat per frame; negative to cool.
+    heat: f32,
+    #[serde(skip)]
+    _marker: PhantomData<K>,
+}
+
+impl Default for ConeProps<Flamethrower> {
+    fn default() -> Self {
+        Self {
+            cost: 0.5,
+            cooldown: Dur::new(0),
+            gcd: Dur::new(0),
+            range: 1.5,
+            arc: FRAC_PI_3,
+            heat: 0.3,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Default for ConeProps<Cryo> {
+    fn default() -> Self {
+        Self {
+            cost: 0.5,
+            cooldown: Dur::new(0),
+            gcd: Dur::new(0),
+            range: 1.5,
+            arc: FRAC_PI_3,
+            heat: -0.45,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: ConeKind> Validate for ConeProps<K> {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("range", self.range);
+        v.check(
+            "arc",
+            self.arc > 0.0 && self.arc <= TAU,
+            "must be between 0 and 2π",
+        );
+        v.finite("heat", self.heat);
+    }
+}
+
+impl<K: ConeKind> AbilityProps for ConeProps<K>
+where
+    ConeProps<K>: Default,
+{
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A cone being sprayed, on the entity spraying it.
+#[derive(Component, Debug)]
+pub struct ConeSpray {
+    /// Heat per frame; negative for cold.
+    pub heat: f32,
+}
+
+/// How many straight edges make up each piece of a sector's arc.
+const SECTOR_SEGMENTS: u32 = 4;
+
+/// A wedge `range` long, spanning `arc` around `FORWARD`, and `height` tall.
+///
+/// It's built from convex pieces, so arcs wider than a half circle work too.
+pub(crate) fn sector(range: f32, arc: f32, height: f32) -> Collider {
+    let pieces = (arc / FRAC_PI_2).ceil().max(1.0) as u32;
+    let step = arc / pieces as f32;
+    let half_height = height * 0.5;
+    let wedges = (0..pieces)
+        .filter_map(|piece| {
+            let start = -arc * 0.5 + step * piece as f32;
+            let mut points = vec![
+                Vec3::new(0.0, -half_height, 0.0),
+                Vec3::new(0.0, half_height, 0.0),
+            ];
+            for segment in 0..=SECTOR_SEGMENTS {
+                let angle = start + step * segment as f32 / SECTOR_SEGMENTS as f32;
+                let edge = Quat::from_rotation_y(angle) * FORWARD * range;
+                points.push(edge.with_y(-half_height));
+                points.push(edge.with_y(half_height));
+            }
+            Collider::convex_hull(&points).map(|hull| (Vec3::ZERO, Quat::IDENTITY, hull))
+        })
+        .collect();
+    Collider::compound(wedges)
+}
+
+/// Everything in a cone of `range` and `arc` from `origin`, the way `user` is
+/// facing, that isn't behind a wall, in a consistent order.
+pub(crate) fn cone_targets(
+    rapier_context: &RapierContext,
+    user: Entity,
+    origin: Vec3,
+    range: f32,
+    arc: f32,
+    transform_q: &Query<&Transform>,
+    wall_q: &Query<(), With<Floor>>,
+) -> Vec<Entity> {
+    let Ok(user_transform) = transform_q.get(user) else {
+        return Vec::new();
+    };
+    let shape = sector(range, arc, PLAYER_HEIGHT);
+    let filter = QueryFilter::new().exclude_collider(user);
+    let mut targets = sorted_intersections(
+        rapier_context,
+        origin,
+        user_transform.rotation,
+        &shape,
+        filter,
+    );
+
+    let wall_pred = |e| wall_q.get(e).is_ok();
+    let wall_filter = QueryFilter::new().predicate(&wall_pred);
+    targets.retain(|&target| {
+        let Ok(target_transform) = transform_q.get(target) else {
+            return false;
+        };
+        // Walls block the cone; we cast in the plane we fire in.
+        let dir = target_transform.translation.to_2d() - origin.to_2d();
+        rapier_context
+            .cast_ray(origin, dir.to_3d(0.0), 1.0, true, wall_filter)
+            .is_none()
+    });
+    targets
+}
+
+fn fire<K: ConeKind>(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<&AbilityOffset>,
+    mut target_q: Query<(&mut Temperature, &TimeDilation)>,
+    transform_q: Query<&Transform>,
+    wall_q: Query<(), With<Floor>>,
+    props: Res<ConeProps<K>>,
+) where
+    ConeProps<K>: Default,
+{
+    let (Ok(transform), Ok(ability_offset)) = (transform_q.get(entity), user_q.get(entity)) else {
+        return;
+    };
+
+    let origin = transform.translation + ability_offset.to_vec();
+    let targets = cone_targets(
+        &rapier_context,
+        entity,
+        origin,
+        props.range,
+        props.arc,
+        &transform_q,
+        &wall_q,
+    );
+    for target in targets {
+        let Ok((mut temperature, dilation)) = target_q.get_mut(target) else {
+            continue;
+        };
+        temperature.heat(props.heat * dilation.factor());
+    }
+
+    commands.entity(entity).insert((
+        ConeSpray { heat: props.heat },
+        Linger::<ConeSpray>::default(),
+    ));
+}
@@ -0,0 +1,431 @@
+//! The parts every ability shares.
+//!
+//! An ability implements `AbilityDef`, and `AbilityDefPlugin` takes care of
+//! its props, cooldowns, costs, and registration, so the ability only needs to
+//! say what it does.
+
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+use bevy_app::Plugin;
+use bevy_app::Startup;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::QueryData;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::EntityCommands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+use super::cooldown::Cooldown;
+use super::magazine::add_magazine;
+use super::magazine::MagazineProps;
+use super::magazine::Magazines;
+use super::noop_ability;
+use super::noop_setup;
+use super::props::Validate;
+use super::tree::run_with_props;
+use super::tree::NodeId;
+use super::tree::SkillTrees;
+use super::Ability;
+use super::AbilityId;
+use super::AbilityMap;
+use super::Left;
+use super::NonArmSlot;
+use super::Right;
+use super::Side;
+use super::SideEnum;
+use super::Slot;
+use crate::multiplayer::ButtonState;
+use crate::multiplayer::Buttons;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::Energy;
+use crate::GameSet;
+use crate::SCHEDULE;
+
+/// Which slots an ability can go in.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SlotKind {
+    /// Both arms, with a primary and secondary action.
+    Arm,
+    /// Both shoulders.
+    Shoulder,
+    Legs,
+    Head,
+}
+
+/// When an ability fires, relative to its button.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Trigger {
+    /// Every frame the button is held.
+    Held,
+    /// Once, when the button is pressed.
+    Press,
+    /// Once, when the button is released.
+    Release,
+}
+
+impl Trigger {
+    fn matches(self, state: ButtonState) -> bool {
+        match self {
+            Trigger::Held => matches!(state, ButtonState::Pressed | ButtonState::Held(_)),
+            Trigger::Press => state == ButtonState::Pressed,
+            Trigger::Release => matches!(state, ButtonState::Released(_)),
+        }
+    }
+}
+
+/// What it takes to use an ability.
+pub trait AbilityProps: Resource + Default + Validate + Serialize + DeserializeOwned {
+    /// How much energy it costs.
+    fn cost(&self) -> f32;
+    /// How long until this ability can be used again.
+    fn cooldown(&self) -> Dur;
+    /// How long until any ability can be used again.
+    fn gcd(&self) -> Dur;
+    /// The ability's magazine, if it has one.
+    fn magazine(&self) -> Option<&MagazineProps> {
+        None
+    }
+}
+
+pub trait AbilityDef: Send + Sync + Sized + 'static {
+    type Props: AbilityProps;
+
+    const SLOTS: SlotKind;
+
+    const TRIGGER: Trigger = Trigger::Held;
+
+    /// When an arm ability's secondary action fires.
+    const SECONDARY_TRIGGER: Trigger = Trigger::Held;
+
+    fn id() -> AbilityId;
+
+    /// Register what this ability does when used.
+    ///
+    /// By the time it runs, we've checked and paid for its costs and cooldowns.
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>>;
+
+    /// Register a check that has to pass before this ability is paid for, for
+    /// abilities that can be refused.
+    fn can_fire<S: Side>(_world: &mut World) -> Option<SystemId<In<Entity>, bool>> {
+        None
+    }
+
+    /// Register the secondary action for arm abilities.
+    ///
+    /// Note: Unlike `fire`, this needs to handle its own costs.
+    fn secondary<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(noop_ability)
+    }
+
+    /// Add anything else this ability needs to the entity that has it.
+    fn setup<S: Side>(_entity: &mut EntityCommands, _props: &Self::Props) {}
+}
+
+/// Adds everything an `AbilityDef` needs to the app.
+pub struct AbilityDefPlugin<A: AbilityDef> {
+    _marker: PhantomData<A>,
+}
+
+impl<A: AbilityDef> Default for AbilityDefPlugin<A> {
+    fn default() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<A: AbilityDef> Plugin for AbilityDefPlugin<A> {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.init_resource::<A::Props>()
+            .add_systems(Startup, register::<A>)
+            .add_systems(
+                SCHEDULE,
+                (cooldown_system::<A, Left>, cooldown_system::<A, Right>).in_set(GameSet::Reset),
+            );
+    }
+}
+
+/// The per-user state of an ability.
+///
+/// Abilities that don't go in a sided slot use `Left`.
+#[derive(Component)]
+pub struct Resources<A: AbilityDef, S: Side> {
+    pub cooldown: Cooldown,
+    /// Props changed by the user's skill tree picks, if they made any.
+    tuned: Option<A::Props>,
+    _marker: PhantomData<(A, S)>,
+}
+
+impl<A: AbilityDef, S: Side> Resources<A, S> {
+    fn new(tuned: Option<A::Props>) -> Self {
+        Self {
+            cooldown: Cooldown::new(),
+            tuned,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// The systems to run once an ability is ready to fire.
+#[derive(Resource)]
+struct Fire<A: AbilityDef, S: Side> {
+    can_fire: Option<SystemId<In<Entity>, bool>>,
+    pay: SystemId<In<Entity>>,
+    system: SystemId<In<Entity>>,
+    _marker: PhantomData<(A, S)>,
+}
+
+/// An arm ability's secondary action.
+#[derive(Resource)]
+struct Secondary<A: AbilityDef, S: Side> {
+    system: SystemId<In<Entity>>,
+    _marker: PhantomData<(A, S)>,
+}
+
+fn ability<A: AbilityDef, S: Side>(world: &mut World) -> Ability {
+    let can_fire = A::can_fire::<S>(world);
+    let pay = world.register_system(pay::<A, S>);
+    let system = A::fire::<S>(world);
+    world.insert_resource(Fire::<A, S> {
+        can_fire,
+        pay,
+        system,
+        _marker: PhantomData,
+    });
+    Ability::new(world, fire::<A, S>, setup::<A, S>)
+}
+
+fn secondary_ability<A: AbilityDef, S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+    let system = A::secondary::<S>(world);
+    world.insert_resource(Secondary::<A, S> {
+        system,
+        _marker: PhantomData,
+    });
+    world.register_system(secondary::<A, S>)
+}
+
+/// The slot an ability is in, for a given side.
+pub fn slot<A: AbilityDef, S: Side>() -> Slot {
+    match A::SLOTS {
+        SlotKind::Arm => Slot::Arm(S::SIDE),
+        SlotKind::Shoulder => Slot::Shoulder(S::SIDE),
+        SlotKind::Legs => Slot::Legs,
+        SlotKind::Head => Slot::Head,
+    }
+}
+
+fn register<A: AbilityDef>(world: &mut World) {
+    let id = A::id();
+
+    match A::SLOTS {
+        SlotKind::Arm => {
+            let left = (
+                ability::<A, Left>(world),
+                secondary_ability::<A, Left>(world),
+            );
+            let right = (
+                ability::<A, Right>(world),
+                secondary_ability::<A, Right>(world),
+            );
+            let noop = world.register_system(noop_setup);
+            let left_secondary = Ability {
+                fire: left.1,
+                setup: noop,
+            };
+            let right_secondary = Ability {
+                fire: right.1,
+                setup: noop,
+            };
+
+            let mut ability_map = world.resource_mut::<AbilityMap>();
+            ability_map.register_arm(SideEnum::Left, id.clone(), left.0, left_secondary);
+            ability_map.register_arm(SideEnum::Right, id, right.0, right_secondary);
+        }
+        SlotKind::Shoulder => {
+            let left = ability::<A, Left>(world);
+            let right = ability::<A, Right>(world);
+
+            let mut ability_map = world.resource_mut::<AbilityMap>();
+            ability_map.register(NonArmSlot::Shoulder(SideEnum::Left), id.clone(), left);
+            ability_map.register(NonArmSlot::Shoulder(SideEnum::Right), id, right);
+        }
+        SlotKind::Legs => {
+            let ability = ability::<A, Left>(world);
+            let mut ability_map = world.resource_mut::<AbilityMap>();
+            ability_map.register(NonArmSlot::Legs, id, ability);
+        }
+        SlotKind::Head => {
+            let ability = ability::<A, Left>(world);
+            let mut ability_map = world.resource_mut::<AbilityMap>();
+            ability_map.register(NonArmSlot::Head, id, ability);
+        }
+    }
+}
+
+fn cooldown_system<A: AbilityDef, S: Side>(
+    mut query: Query<(&mut Resources<A, S>, &TimeDilation)>,
+) {
+    for (mut resources, time_dilation) in &mut query {
+        resources.cooldown.tick(time_dilation);
+    }
+}
+
+fn setup<A: AbilityDef, S: Side>(
+    In((entity, picked)): In<(Entity, BTreeSet<NodeId>)>,
+    mut commands: Commands,
+    props: Res<A::Props>,
+    trees: Res<SkillTrees>,
+) {
+    let tuned = trees.tune(&A::id(), &*props, &picked);
+    let props = tuned.as_ref().unwrap_or(&*props);
+    let mut entity = commands.entity(entity);
+    if let Some(magazine) = props.magazine() {
+        add_magazine(&mut entity, slot::<A, S>(), magazine);
+    }
+    A::setup::<S>(&mut entity, props);
+    entity.try_insert(Resources::<A, S>::new(tuned));
+}
+
+/// Runs one of an ability's systems, with its user's tuned props standing in
+/// for the shared ones.
+fn run_tuned<A: AbilityDef, S: Side, O: 'static>(
+    world: &mut World,
+    entity: Entity,
+    system: SystemId<In<Entity>, O>,
+) -> Option<O> {
+    let tuned = world
+        .get_mut::<Resources<A, S>>(entity)
+        .and_then(|mut resources| resources.tuned.take());
+    let Some(mut tuned) = tuned else {
+        return world
+            .run_system_with_input(system, entity)
+            .inspect_err(|err| tracing::warn!(?entity, %err, "Could not run ability"))
+            .ok();
+    };
+
+    let output = run_with_props(world, &mut tuned, system, entity);
+    if let Some(mut resources) = world.get_mut::<Resources<A, S>>(entity) {
+        resources.tuned = Some(tuned);
+    }
+    output
+}
+
+fn secondary<A: AbilityDef, S: Side>(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<&Buttons>,
+    secondary: Res<Secondary<A, S>>,
+) {
+    let Ok(buttons) = user_q.get(entity) else {
+        return;
+    };
+    let Some(action) = Slot::ArmSecondary(S::SIDE).action() else {
+        return;
+    };
+    if !A::SECONDARY_TRIGGER.matches(buttons.state(action)) {
+        return;
+    }
+
+    let system = secondary.system;
+    commands.queue(move |world: &mut World| {
+        run_tuned::<A, S, _>(world, entity, system);
+    });
+}
+
+#[derive(QueryData)]
+#[query_data(mutable)]
+struct FireQuery<A: AbilityDef, S: Side> {
+    gcd: &'static mut Cooldown,
+    energy: &'static mut Energy,
+    resources: &'static mut Resources<A, S>,
+    time_dilation: &'static TimeDilation,
+    buttons: &'static Buttons,
+    magazines: Option<&'static mut Magazines>,
+}
+
+fn fire<A: AbilityDef, S: Side>(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<FireQuery<A, S>>,
+    props: Res<A::Props>,
+    fire: Res<Fire<A, S>>,
+) {
+    let Ok(user) = user_q.get(entity) else {
+        return;
+    };
+
+    let slot = slot::<A, S>();
+    let Some(action) = slot.action() else {
+        return;
+    };
+    if !A::TRIGGER.matches(user.buttons.state(action)) {
+        return;
+    }
+
+    if !user.gcd.is_available(user.time_dilation) {
+        return;
+    }
+
+    let magazine = user.magazines.and_then(|magazines| magazines.get(slot));
+    if magazine.is_some_and(|magazine| !magazine.can_fire()) {
+        return;
+    }
+
+    let props = user.resources.tuned.as_ref().unwrap_or(&*props);
+    if !user.resources.cooldown.is_available(user.time_dilation) || user.energy.cur < props.cost() {
+        return;
+    }
+
+    let Fire {
+        can_fire,
+        pay,
+        system,
+        ..
+    } = *fire;
+    commands.queue(move |world: &mut World| {
+        if let Some(can_fire) = can_fire {
+            if run_tuned::<A, S, _>(world, entity, can_fire) != Some(true) {
+                return;
+            }
+        }
+        if let Err(err) = world.run_system_with_input(pay, entity) {
+            tracing::warn!(?entity, %err, "Could not pay for ability");
+            return;
+        }
+        run_tuned::<A, S, _>(world, entity, system);
+    });
+}
+
+/// Charges for an ability that we've checked is ready to fire.
+fn pay<A: AbilityDef, S: Side>(
+    In(entity): In<Entity>,
+    mut user_q: Query<FireQuery<A, S>>,
+    props: Res<A::Props>,
+) {
+    let Ok(mut user) = user_q.get_mut(entity) else {
+        return;
+    };
+
+    let props = user.resources.tuned.as_ref().unwrap_or(&*props);
+    let (cost, cooldown, gcd) = (props.cost(), props.cooldown(), props.gcd());
+    user.energy.try_use(cost);
+    user.resources.cooldown.set(cooldown);
+    user.gcd.set(gcd);
+    if let Some(magazine) = user
+        .magazines
+        .as_mut()
+        .and_then(|magazines| magazines.get_mut(slot::<A, S>()))
+    {
+        magazine.try_use();
+    }
+}
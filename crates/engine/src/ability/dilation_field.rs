@@ -0,0 +1,229 @@
+use std::marker::PhantomData;
+
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Quat;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::collision::sorted_intersections;
+use crate::level::InLevel;
+use crate::lifecycle::Lifetime;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::GameSet;
+use crate::Health;
+use crate::Target;
+use crate::To2d;
+use crate::To3d;
+use crate::PLAYER_HEIGHT;
+use crate::SCHEDULE;
+
+pub struct DilationFieldPlugin;
+impl Plugin for DilationFieldPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<DilationFieldAbility<SlowField>>::default(),
+            AbilityDefPlugin::<DilationFieldAbility<HasteField>>::default(),
+        ))
+        .add_systems(SCHEDULE, field_system.in_set(GameSet::Stuff));
+    }
+}
+
+pub trait DilationFieldKind: Send + Sync + Sized + 'static {
+    fn id() -> AbilityId;
+}
+
+pub struct SlowField;
+
+impl DilationFieldKind for SlowField {
+    fn id() -> AbilityId {
+        AbilityId::from("slow_field")
+    }
+}
+
+pub struct HasteField;
+
+impl DilationFieldKind for HasteField {
+    fn id() -> AbilityId {
+        AbilityId::from("haste_field")
+    }
+}
+
+/// Plants a field at the user's target that slows down (or speeds up)
+/// everything inside it.
+pub struct DilationFieldAbility<K: DilationFieldKind> {
+    _marker: PhantomData<K>,
+}
+impl<K: DilationFieldKind> AbilityDef for DilationFieldAbility<K>
+where
+    DilationFieldProps<K>: Default,
+{
+    type Props = DilationFieldProps<K>;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        K::id()
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire::<K>)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct DilationFieldProps<K: DilationFieldKind> {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    /// How far away the field can be planted.
+    range: f32,
+    radius: f32,
+    lifetime: Dur,
+    /// The `TimeDilation` effect on everything inside; negative to slow.
+    amount: f32,
+    #[serde(skip)]
+    _marker: PhantomData<K>,
+}
+
+impl Default for DilationFieldProps<SlowField> {
+    fn default() -> Self {
+        Self {
+            cost: 40.0,
+            cooldown: Dur::new(480),
+            gcd: Dur::new(30),
+            range: 5.0,
+            radius: 1.5,
+            lifetime: Dur::new(300),
+            amount: -1.0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Default for DilationFieldProps<HasteField> {
+    fn default() -> Self {
+        Self {
+            cost: 40.0,
+            cooldown: Dur::new(480),
+            gcd: Dur::new(30),
+            range: 5.0,
+            radius: 1.5,
+            lifetime: Dur::new(300),
+            amount: 0.5,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: DilationFieldKind> Validate for DilationFieldProps<K> {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.non_negative("range", self.range);
+        v.positive("radius", self.radius);
+        v.positive_duration("lifetime", self.lifetime);
+        v.finite("amount", self.amount);
+    }
+}
+
+impl<K: DilationFieldKind> AbilityProps for DilationFieldProps<K>
+where
+    DilationFieldProps<K>: Default,
+{
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A time dilation field, centered on the floor.
+#[derive(Component, Debug)]
+pub struct DilationField {
+    pub radius: f32,
+    /// Negative for slow.
+    pub amount: f32,
+}
+
+fn fire<K: DilationFieldKind>(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<(&Transform, &Target)>,
+    props: Res<DilationFieldProps<K>>,
+) where
+    DilationFieldProps<K>: Default,
+{
+    let Ok((transform, target)) = user_q.get(entity) else {
+        return;
+    };
+
+    let start = transform.translation.to_2d();
+    let center = start + (target.0 - start).clamp_length_max(props.range);
+
+    commands.spawn((
+        Transform::from_translation(center.to_3d(0.0)),
+        DilationField {
+            radius: props.radius,
+            amount: props.amount,
+        },
+        Lifetime::new(props.lifetime),
+        Health::new(1.0),
+        TimeDilation::default(),
+        InLevel,
+    ));
+}
+
+fn field_system(
+    rapier_context: ReadDefaultRapierContext,
+    field_q: Query<(&Transform, &DilationField)>,
+    mut target_q: Query<&mut TimeDilation>,
+) {
+    for (transform, field) in &field_q {
+        // Tall enough to catch anything standing in it, or flying over it.
+        let shape = Collider::cylinder(PLAYER_HEIGHT, field.radius);
+        let targets = sorted_intersections(
+            &rapier_context,
+            transform.translation,
+            Quat::IDENTITY,
+            &shape,
+            QueryFilter::new(),
+        );
+
+        for target in targets {
+            if let Ok(mut dilation) = target_q.get_mut(target) {
+                // This lasts a frame, so it ends shortly after leaving the field.
+                dilation.add_effect(field.amount, Dur::new(1));
+            }
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::Has;
+use bevy_ecs::query::With;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::heal_beam::lock_on;
+use super::linger::Linger;
+use super::linger::LingerPlugin;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::time::Dur;
+use crate::AbilityOffset;
+use crate::Ally;
+use crate::CharacterMarker;
+use crate::Enemy;
+use crate::Energy;
+use crate::Shootable;
+
+pub struct EnergyBeamPlugin;
+impl Plugin for EnergyBeamPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<EnergyBeamAbility>::default(),
+            LingerPlugin::<EnergyBeam>::default(),
+        ));
+    }
+}
+
+/// Locks on to a character like the heal beam, and gives them our energy.
+///
+/// Against foes, it runs the other way: it's free, and takes their energy for
+/// us instead.
+pub struct EnergyBeamAbility;
+impl AbilityDef for EnergyBeamAbility {
+    type Props = EnergyBeamProps;
+
+    const SLOTS: SlotKind = SlotKind::Arm;
+
+    fn id() -> AbilityId {
+        AbilityId::from("energy_beam")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct EnergyBeamProps {
+    /// Energy per frame; also how much we move per frame.
+    ///
+    /// Stealing is free, and we only know which way energy flows once we've
+    /// locked on, so the beam pays this itself rather than up front.
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    range: f32,
+    /// The fraction of the energy moved that arrives.
+    efficiency: f32,
+}
+
+impl Default for EnergyBeamProps {
+    fn default() -> Self {
+        Self {
+            cost: 0.4,
+            cooldown: Dur::new(0),
+            gcd: Dur::new(0),
+            range: 4.0,
+            efficiency: 0.75,
+        }
+    }
+}
+
+impl Validate for EnergyBeamProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("range", self.range);
+        v.check(
+            "efficiency",
+            (0.0..=1.0).contains(&self.efficiency),
+            "must be between 0 and 1",
+        );
+    }
+}
+
+impl AbilityProps for EnergyBeamProps {
+    fn cost(&self) -> f32 {
+        0.0
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// An energy beam, on the entity firing it.
+#[derive(Component, Debug)]
+pub struct EnergyBeam {
+    pub target: Entity,
+    /// Whether energy is flowing from the target to us, rather than the other
+    /// way.
+    pub stealing: bool,
+}
+
+fn fire(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<(
+        &Transform,
+        &AbilityOffset,
+        Has<Ally>,
+        Has<Enemy>,
+        Option<&EnergyBeam>,
+    )>,
+    target_q: Query<(&Transform, Has<Ally>, Has<Enemy>), With<CharacterMarker>>,
+    mut energy_q: Query<&mut Energy>,
+    shootable_q: Query<(), With<Shootable>>,
+    props: Res<EnergyBeamProps>,
+) {
+    let Ok((transform, ability_offset, ally, enemy, beam)) = user_q.get(entity) else {
+        return;
+    };
+    let origin = transform.translation + ability_offset.to_vec();
+
+    let target = lock_on(
+        &rapier_context,
+        &shootable_q,
+        entity,
+        transform,
+        origin,
+        beam.map(|beam| beam.target),
+        props.range,
+        |target| target_q.get(target).ok().map(|(t, _, _)| t.translation),
+    );
+
+    let Some((target, target_ally, target_enemy)) =
+        target.and_then(|target| target_q.get(target).ok().map(|(_, a, e)| (target, a, e)))
+    else {
+        commands
+            .entity(entity)
+            .remove::<(EnergyBeam, Linger<EnergyBeam>)>();
+        return;
+    };
+    let stealing = !((ally && target_ally) || (enemy && target_enemy));
+
+    let Ok([mut user_energy, mut target_energy]) = energy_q.get_many_mut([entity, target]) else {
+        return;
+    };
+    if stealing {
+        let taken = target_energy.cur.min(props.cost);
+        target_energy.cur -= taken;
+        user_energy.cur = (user_energy.cur + taken * props.efficiency).min(user_energy.max);
+    } else if user_energy.try_use(props.cost) {
+        target_energy.cur =
+            (target_energy.cur + props.cost * props.efficiency).min(target_energy.max);
+    } else {
+        commands
+            .entity(entity)
+            .remove::<(EnergyBeam, Linger<EnergyBeam>)>();
+        return;
+    }
+
+    commands.entity(entity).insert((
+        EnergyBeam { target, stealing },
+        Linger::<EnergyBeam>::default(),
+    ));
+}
@@ -1,6 +1,8 @@
 use bevy_app::Plugin;
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
+use bevy_ecs::event::EventWriter;
+use bevy_ecs::query::With;
 use bevy_ecs::schedule::IntoSystemConfigs;
 use bevy_ecs::system::Commands;
 use bevy_ecs::system::In;
@@ -13,18 +15,24 @@ use bevy_rapier3d::prelude::Collider;
 use bevy_rapier3d::prelude::ExternalForce;
 use bevy_rapier3d::prelude::LockedAxes;
 use bevy_rapier3d::prelude::QueryFilter;
-use bevy_rapier3d::prelude::QueryFilterFlags;
 use bevy_rapier3d::prelude::RigidBody;
 use bevy_rapier3d::prelude::Sensor;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
 
+use super::projectile::Projectile;
+use super::props::Validate;
+use super::props::Validator;
 use crate::collision::TrackCollisionBundle;
 use crate::collision::TrackCollisions;
+use crate::level::Floor;
 use crate::level::InLevel;
 use crate::status_effect::StatusProps;
 use crate::status_effect::TimeDilation;
 use crate::time::Dur;
+use crate::DamageEvent;
 use crate::GameSet;
 use crate::Health;
 use crate::MassBundle;
@@ -51,7 +59,7 @@ pub struct ExplosionCallback {
     pub system: SystemId<In<Entity>>,
 }
 
-#[derive(Debug, Copy, Clone, Component)]
+#[derive(Debug, Copy, Clone, Component, Serialize, Deserialize)]
 pub struct ExplosionProps {
     pub damage: f32,
     pub force: f32,
@@ -62,13 +70,29 @@ pub struct ExplosionProps {
 }
 
 // TODO: Get rid of this enum.
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
 pub enum ExplosionKind {
     FragGrenade,
     HealGrenade,
     SeekerRocket,
 }
 
+impl Validate for ExplosionProps {
+    fn validate(&self, v: &mut Validator) {
+        v.finite("damage", self.damage);
+        v.finite("force", self.force);
+        v.positive("min_radius", self.min_radius);
+        v.positive("max_radius", self.max_radius);
+        v.check(
+            "max_radius",
+            self.max_radius >= self.min_radius,
+            "must be at least min_radius",
+        );
+        // Explosions grow over their duration, so it can't be 0.
+        v.positive_duration("duration", self.duration);
+    }
+}
+
 #[derive(Debug, Component)]
 pub struct Explosion {
     pub damage: f32,
@@ -77,6 +101,8 @@ pub struct Explosion {
     pub max_radius: f32,
     pub growth_rate: f32,
     pub kind: ExplosionKind,
+    /// Who gets the credit for its damage, if anyone.
+    pub owner: Option<Entity>,
 }
 
 impl From<&ExplosionProps> for Explosion {
@@ -88,6 +114,7 @@ impl From<&ExplosionProps> for Explosion {
             max_radius: props.max_radius,
             growth_rate: (props.max_radius - props.min_radius) / props.duration,
             kind: props.kind,
+            owner: None,
         }
     }
 }
@@ -95,9 +122,9 @@ impl From<&ExplosionProps> for Explosion {
 fn explosion_callback(
     In(entity): In<Entity>,
     mut commands: Commands,
-    query: Query<(&Transform, &ExplosionProps)>,
+    query: Query<(&Transform, &ExplosionProps, Option<&Projectile>)>,
 ) {
-    let Ok((transform, props)) = query.get(entity) else {
+    let Ok((transform, props, projectile)) = query.get(entity) else {
         return;
     };
     let mut transform = *transform;
@@ -123,7 +150,10 @@ fn explosion_callback(
             .into(),
             collisions: TrackCollisionBundle::on(),
         },
-        Explosion::from(props),
+        Explosion {
+            owner: projectile.map(|projectile| projectile.shooter),
+            ..Explosion::from(props)
+        },
         Sensor,
         Health::new_with_delay(0.0, props.duration),
     ));
@@ -142,11 +172,14 @@ fn explosion_collision_system(
     rapier_context: ReadDefaultRapierContext,
     explosion_q: Query<(&Explosion, &Transform, &TrackCollisions, &TimeDilation)>,
     mut target_q: Query<(&Transform, &mut Health, &mut ExternalForce, &TimeDilation)>,
+    wall_q: Query<(), With<Floor>>,
+    projectile_q: Query<(), With<Projectile>>,
+    mut damage_events: EventWriter<DamageEvent>,
 ) {
-    let wall_filter = QueryFilter {
-        flags: QueryFilterFlags::ONLY_FIXED,
-        ..Default::default()
-    };
+    // Only the level blocks explosions; other fixed things, like turrets and
+    // switches, get hit instead.
+    let wall_pred = |e| wall_q.get(e).is_ok();
+    let wall_filter = QueryFilter::new().predicate(&wall_pred);
     for (explosion, transform, colliding, dilation) in &explosion_q {
         // Dilated explosions have their lifetimes and grow rates affected, so
         // their damage should be too. This way, a full explosion always does a
@@ -171,7 +204,15 @@ fn explosion_collision_system(
                         continue;
                     }
                 }
-                health.take(explosion_damage, target_dilation);
+                let amount = health.take(explosion_damage, target_dilation);
+                // Setting off other projectiles isn't damage to anyone.
+                if let Some(owner) = explosion.owner.filter(|_| !projectile_q.contains(target)) {
+                    damage_events.send(DamageEvent {
+                        source: owner,
+                        target,
+                        amount,
+                    });
+                }
                 let dir = (target_transform.translation.to_2d() - transform.translation.to_2d())
                     .normalize_or_zero()
                     .to_3d(0.0);
@@ -1,7 +1,4 @@
-use std::marker::PhantomData;
-
 use bevy_app::Plugin;
-use bevy_app::Startup;
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::query::QueryData;
@@ -12,6 +9,7 @@ use bevy_ecs::system::In;
 use bevy_ecs::system::Query;
 use bevy_ecs::system::Res;
 use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
 use bevy_ecs::world::World;
 use bevy_hierarchy::BuildChildren;
 use bevy_hierarchy::ChildBuild;
@@ -25,16 +23,17 @@ use bevy_rapier3d::prelude::Sensor;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::GlobalTransform;
 use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
 
-use super::cooldown::Cooldown;
-use super::Ability;
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
 use super::AbilityId;
-use super::AbilityMap;
-use super::Left;
-use super::NonArmSlot;
-use super::Right;
 use super::Side;
-use super::SideEnum;
 use crate::collision::TrackCollisionBundle;
 use crate::collision::TrackCollisions;
 use crate::level::InLevel;
@@ -42,7 +41,6 @@ use crate::status_effect::StatusProps;
 use crate::status_effect::TimeDilation;
 use crate::time::Dur;
 use crate::AbilityOffset;
-use crate::Energy;
 use crate::FootOffset;
 use crate::GameSet;
 use crate::Health;
@@ -56,20 +54,31 @@ use crate::SCHEDULE;
 pub struct GravityBallPlugin;
 impl Plugin for GravityBallPlugin {
     fn build(&self, app: &mut bevy_app::App) {
-        app.insert_resource(GravityBallProps::default())
-            .add_systems(Startup, register)
+        app.add_plugins(AbilityDefPlugin::<GravityBallAbility>::default())
             .add_systems(
                 SCHEDULE,
-                (
-                    (cooldown_system::<Left>, cooldown_system::<Right>).in_set(GameSet::Reset),
-                    (activation_system, collision_system).in_set(GameSet::Stuff),
-                ),
+                (activation_system, collision_system).in_set(GameSet::Stuff),
             );
     }
 }
 
-#[derive(Debug, Resource)]
-struct GravityBallProps {
+pub struct GravityBallAbility;
+impl AbilityDef for GravityBallAbility {
+    type Props = GravityBallProps;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        AbilityId::from("gravity_ball")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct GravityBallProps {
     cost: f32,
     cooldown: Dur,
     gcd: Dur,
@@ -97,6 +106,34 @@ impl Default for GravityBallProps {
     }
 }
 
+impl Validate for GravityBallProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("radius", self.radius);
+        v.positive("effect_radius", self.effect_radius);
+        v.duration("duration", self.duration);
+        v.duration("activation_delay", self.activation_delay);
+        v.finite("speed", self.speed);
+        v.finite("surface_a", self.surface_a);
+    }
+}
+
+impl AbilityProps for GravityBallProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
 impl GravityBallProps {
     /// The acceleration due to this ball will be the result value, divided by
     /// distance squared.
@@ -112,74 +149,22 @@ impl GravityBallProps {
     }
 }
 
-fn register(world: &mut World) {
-    let id = AbilityId::from("gravity_ball");
-
-    let left = Ability::new(world, fire::<Left>, setup::<Left>);
-    let right = Ability::new(world, fire::<Right>, setup::<Right>);
-
-    let mut ability_map = world.get_resource_mut::<AbilityMap>().unwrap();
-
-    ability_map.register(NonArmSlot::Shoulder(SideEnum::Left), id.clone(), left);
-    ability_map.register(NonArmSlot::Shoulder(SideEnum::Right), id.clone(), right);
-}
-
-fn cooldown_system<S: Side>(mut query: Query<(&mut Resources<S>, &TimeDilation)>) {
-    for (mut resources, time_dilation) in &mut query {
-        resources.cooldown.tick(time_dilation);
-    }
-}
-
-fn setup<S: Side>(entity: In<Entity>, mut commands: Commands) {
-    commands.entity(*entity).try_insert(Resources::<S>::new());
-}
-
-#[derive(Component)]
-struct Resources<S: Side> {
-    cooldown: Cooldown,
-    _marker: PhantomData<S>,
-}
-impl<S: Side> Resources<S> {
-    fn new() -> Self {
-        Self {
-            cooldown: Cooldown::new(),
-            _marker: PhantomData,
-        }
-    }
-}
-
 #[derive(QueryData)]
-#[query_data(mutable)]
-struct FireQuery<S: Side> {
-    gcd: &'static mut Cooldown,
-    energy: &'static mut Energy,
+struct FireQuery {
     transform: &'static Transform,
     velocity: &'static Velocity,
     ability_offset: &'static AbilityOffset,
-    resources: &'static mut Resources<S>,
-    time_dilation: &'static TimeDilation,
 }
-fn fire<S: Side>(
+fn fire(
     In(entity): In<Entity>,
     mut commands: Commands,
-    mut user_q: Query<FireQuery<S>>,
+    user_q: Query<FireQuery>,
     props: Res<GravityBallProps>,
 ) {
-    let Ok(mut user) = user_q.get_mut(entity) else {
+    let Ok(user) = user_q.get(entity) else {
         return;
     };
 
-    if !user.gcd.is_available(user.time_dilation) {
-        return;
-    }
-
-    if user.resources.cooldown.is_available(user.time_dilation) && user.energy.try_use(props.cost) {
-        user.resources.cooldown.set(props.cooldown);
-        user.gcd.set(props.gcd);
-    } else {
-        return;
-    }
-
     let dir = user.transform.rotation * FORWARD;
     let position =
         user.transform.translation + dir * (PLAYER_R + props.radius) + user.ability_offset.to_vec();
@@ -2,7 +2,6 @@ use std::f32::consts::PI;
 use std::marker::PhantomData;
 
 use bevy_app::Plugin;
-use bevy_app::Startup;
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::query::QueryData;
@@ -11,6 +10,7 @@ use bevy_ecs::system::In;
 use bevy_ecs::system::Query;
 use bevy_ecs::system::Res;
 use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
 use bevy_ecs::world::World;
 use bevy_math::Vec3;
 use bevy_rapier3d::prelude::Collider;
@@ -20,29 +20,35 @@ use bevy_rapier3d::prelude::LockedAxes;
 use bevy_rapier3d::prelude::Restitution;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
 
-use super::cooldown::Cooldown;
+use super::def::slot;
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::def::Trigger;
 use super::explosion::ExplosionCallback;
 use super::explosion::ExplosionKind;
 use super::explosion::ExplosionProps;
-use super::Ability;
+use super::magazine::MagazineProps;
+use super::projectile::Projectile;
+use super::projectile::ProjectileKind;
+use super::props::Validate;
+use super::props::Validator;
 use super::AbilityId;
-use super::AbilityMap;
-use super::Left;
-use super::NonArmSlot;
-use super::Right;
 use super::Side;
-use super::SideEnum;
 use crate::collision::TrackCollisionBundle;
 use crate::level::InLevel;
 use crate::lifecycle::DeathCallback;
 use crate::lifecycle::Lifetime;
+use crate::multiplayer::ButtonState;
+use crate::multiplayer::Buttons;
 use crate::physics::G;
 use crate::status_effect::StatusProps;
-use crate::status_effect::TimeDilation;
 use crate::time::Dur;
 use crate::AbilityOffset;
-use crate::Energy;
 use crate::Health;
 use crate::Libm;
 use crate::MassBundle;
@@ -53,7 +59,6 @@ use crate::To2d;
 use crate::To3d;
 use crate::FORWARD;
 use crate::PLAYER_R;
-use crate::SCHEDULE;
 
 /// Calculate the initial velocity of a projectile thrown at 45 degrees up, so
 /// that it will land at target.
@@ -83,20 +88,70 @@ fn calculate_initial_vel(spawn: Vec3, target: Vec3) -> Velocity {
     }
 }
 
-#[derive(Debug, Resource)]
+#[derive(Debug, Resource, Serialize, Deserialize)]
 pub struct GrenadeProps<G: Grenade> {
     cost: f32,
     cooldown: Dur,
     gcd: Dur,
+    /// How long until it goes off; the fuse starts when the button is
+    /// pressed, so holding it cooks the grenade.
     delay: Dur,
     radius: f32,
     health: f32,
     explosion: ExplosionProps,
     mass: f32,
+    /// Grenades don't have a magazine unless given one here. Shoulders have no
+    /// secondary, so it has to reload on its own.
+    #[serde(default)]
+    magazine: Option<MagazineProps>,
+    #[serde(skip)]
     _marker: PhantomData<G>,
 }
-impl GrenadeProps<FragGrenade> {
-    fn new() -> Self {
+
+impl<G: Grenade> Validate for GrenadeProps<G> {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.duration("delay", self.delay);
+        v.positive("radius", self.radius);
+        v.positive("health", self.health);
+        v.nested("explosion", &self.explosion);
+        v.positive("mass", self.mass);
+        if let Some(magazine) = &self.magazine {
+            v.nested("magazine", magazine);
+            v.check(
+                "magazine.auto_reload",
+                magazine.auto_reload,
+                "must be true, as nothing else reloads grenades",
+            );
+        }
+    }
+}
+
+impl<G: Grenade> AbilityProps for GrenadeProps<G>
+where
+    GrenadeProps<G>: Default,
+{
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+
+    fn magazine(&self) -> Option<&MagazineProps> {
+        self.magazine.as_ref()
+    }
+}
+
+impl Default for GrenadeProps<FragGrenade> {
+    fn default() -> Self {
         Self {
             cost: 30.0,
             cooldown: Dur::new(60),
@@ -113,13 +168,14 @@ impl GrenadeProps<FragGrenade> {
                 kind: ExplosionKind::FragGrenade,
             },
             mass: 1.5,
+            magazine: None,
             _marker: PhantomData,
         }
     }
 }
 
-impl GrenadeProps<HealGrenade> {
-    fn new() -> Self {
+impl Default for GrenadeProps<HealGrenade> {
+    fn default() -> Self {
         Self {
             cost: 50.0,
             cooldown: Dur::new(60),
@@ -136,6 +192,7 @@ impl GrenadeProps<HealGrenade> {
                 kind: ExplosionKind::HealGrenade,
             },
             mass: 1.0,
+            magazine: None,
             _marker: PhantomData,
         }
     }
@@ -144,18 +201,10 @@ impl GrenadeProps<HealGrenade> {
 pub struct GrenadePlugin;
 impl Plugin for GrenadePlugin {
     fn build(&self, app: &mut bevy_app::App) {
-        app.insert_resource(GrenadeProps::<FragGrenade>::new())
-            .insert_resource(GrenadeProps::<HealGrenade>::new())
-            .add_systems(Startup, (register::<FragGrenade>, register::<HealGrenade>))
-            .add_systems(
-                SCHEDULE,
-                (
-                    cooldown_system::<Left, FragGrenade>,
-                    cooldown_system::<Right, FragGrenade>,
-                    cooldown_system::<Left, HealGrenade>,
-                    cooldown_system::<Right, HealGrenade>,
-                ),
-            );
+        app.add_plugins((
+            AbilityDefPlugin::<GrenadeAbility<FragGrenade>>::default(),
+            AbilityDefPlugin::<GrenadeAbility<HealGrenade>>::default(),
+        ));
     }
 }
 
@@ -166,38 +215,25 @@ pub trait Grenade: Send + Sync + Sized + 'static + Component {
     fn explosion_radius(&self) -> f32;
 }
 
-fn register<G: Grenade>(world: &mut World) {
-    let id = G::id();
-    let left = Ability::new(world, fire::<Left, G>, setup::<Left, G>);
-    let right = Ability::new(world, fire::<Right, G>, setup::<Right, G>);
-    let mut ability_map = world.get_resource_mut::<AbilityMap>().unwrap();
-    ability_map.register(NonArmSlot::Shoulder(SideEnum::Left), id.clone(), left);
-    ability_map.register(NonArmSlot::Shoulder(SideEnum::Right), id.clone(), right);
+pub struct GrenadeAbility<G: Grenade> {
+    _marker: PhantomData<G>,
 }
+impl<G: Grenade> AbilityDef for GrenadeAbility<G>
+where
+    GrenadeProps<G>: Default,
+{
+    type Props = GrenadeProps<G>;
 
-fn cooldown_system<S: Side, G: Grenade>(mut query: Query<(&mut Resources<S, G>, &TimeDilation)>) {
-    for (mut resources, time_dilation) in &mut query {
-        resources.cooldown.tick(time_dilation);
-    }
-}
+    const SLOTS: SlotKind = SlotKind::Shoulder;
 
-fn setup<S: Side, G: Grenade>(entity: In<Entity>, mut commands: Commands) {
-    commands
-        .entity(*entity)
-        .try_insert(Resources::<S, G>::new());
-}
+    const TRIGGER: Trigger = Trigger::Release;
 
-#[derive(Component)]
-struct Resources<S: Side, G: Grenade> {
-    cooldown: Cooldown,
-    _marker: PhantomData<(S, G)>,
-}
-impl<S: Side, G: Grenade> Resources<S, G> {
-    fn new() -> Self {
-        Self {
-            cooldown: Cooldown::new(),
-            _marker: PhantomData,
-        }
+    fn id() -> AbilityId {
+        G::id()
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire::<G, S>)
     }
 }
 
@@ -242,38 +278,34 @@ impl Grenade for HealGrenade {
 }
 
 #[derive(QueryData)]
-#[query_data(mutable)]
-struct FireQuery<S: Side, G: Grenade> {
-    gcd: &'static mut Cooldown,
-    energy: &'static mut Energy,
+struct FireQuery {
     transform: &'static Transform,
     ability_offset: &'static AbilityOffset,
-    resources: &'static mut Resources<S, G>,
-    time_dilation: &'static TimeDilation,
     target: &'static Target,
+    buttons: &'static Buttons,
 }
 
-fn fire<S: Side, G: Grenade>(
+fn fire<G: Grenade, S: Side>(
     In(entity): In<Entity>,
     mut commands: Commands,
-    mut user_q: Query<FireQuery<S, G>>,
+    user_q: Query<FireQuery>,
     props: Res<GrenadeProps<G>>,
     explosion_callback: Res<ExplosionCallback>,
-) {
-    let Ok(mut user) = user_q.get_mut(entity) else {
+) where
+    GrenadeProps<G>: Default,
+{
+    let Ok(user) = user_q.get(entity) else {
         return;
     };
 
-    if !user.gcd.is_available(user.time_dilation) {
-        return;
-    }
-
-    if user.resources.cooldown.is_available(user.time_dilation) && user.energy.try_use(props.cost) {
-        user.resources.cooldown.set(props.cooldown);
-        user.gcd.set(props.gcd);
-    } else {
-        return;
-    }
+    // The fuse has been burning for as long as the button was held.
+    let cooked = slot::<GrenadeAbility<G>, S>()
+        .action()
+        .map(|action| match user.buttons.state(action) {
+            ButtonState::Released(held) => held,
+            _ => 0,
+        })
+        .unwrap_or(0);
 
     let dir = user.transform.rotation * FORWARD;
     let position = user.transform.translation
@@ -283,8 +315,7 @@ fn fire<S: Side, G: Grenade>(
 
     commands.spawn((
         Object {
-            transform: Transform::from_translation(position)
-                .with_scale(Vec3::splat(props.radius)),
+            transform: Transform::from_translation(position).with_scale(Vec3::splat(props.radius)),
             collider: Collider::ball(1.0),
             foot_offset: (-props.radius).into(),
             mass: MassBundle::new(props.mass),
@@ -302,6 +333,11 @@ fn fire<S: Side, G: Grenade>(
         },
         props.explosion,
         Shootable,
+        Projectile {
+            kind: ProjectileKind::Grenade,
+            shooter: entity,
+            damage: 0.0,
+        },
         G::new(&props),
         Friction {
             coefficient: 100.0,
@@ -311,7 +347,7 @@ fn fire<S: Side, G: Grenade>(
             coefficient: 0.0,
             ..Default::default()
         },
-        Lifetime::new(props.delay),
+        Lifetime::new(props.delay.saturating_sub(Dur::new(cooked))),
         DeathCallback::new(explosion_callback.system),
         Health::new(props.health),
     ));
@@ -0,0 +1,336 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::Has;
+use bevy_ecs::query::With;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::status_effect::Temperature;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::Ally;
+use crate::CharacterMarker;
+use crate::Enemy;
+use crate::Energy;
+use crate::GameSet;
+use crate::SCHEDULE;
+
+pub struct HeadPlugin;
+impl Plugin for HeadPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<ScanAbility>::default(),
+            AbilityDefPlugin::<TargetingComputerAbility>::default(),
+            AbilityDefPlugin::<OverclockAbility>::default(),
+        ))
+        .add_systems(
+            SCHEDULE,
+            (revealed_system, targeting_system, overclock_system).in_set(GameSet::Stuff),
+        );
+    }
+}
+
+/// Reveals nearby foes, even through walls, for a while.
+pub struct ScanAbility;
+impl AbilityDef for ScanAbility {
+    type Props = ScanProps;
+
+    const SLOTS: SlotKind = SlotKind::Head;
+
+    fn id() -> AbilityId {
+        AbilityId::from("scan")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(scan)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct ScanProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    radius: f32,
+    duration: Dur,
+}
+
+impl Default for ScanProps {
+    fn default() -> Self {
+        Self {
+            cost: 30.0,
+            cooldown: Dur::new(600),
+            gcd: Dur::new(30),
+            radius: 12.0,
+            duration: Dur::new(300),
+        }
+    }
+}
+
+impl Validate for ScanProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("radius", self.radius);
+        v.positive_duration("duration", self.duration);
+    }
+}
+
+impl AbilityProps for ScanProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A character that's been spotted by a scan.
+#[derive(Component, Debug)]
+pub struct Revealed {
+    remaining: Dur,
+}
+
+fn scan(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<(&Transform, Has<Ally>, Has<Enemy>)>,
+    target_q: Query<(Entity, &Transform, Has<Ally>, Has<Enemy>), With<CharacterMarker>>,
+    props: Res<ScanProps>,
+) {
+    let Ok((transform, ally, enemy)) = user_q.get(entity) else {
+        return;
+    };
+
+    for (target, target_transform, target_ally, target_enemy) in &target_q {
+        let foe = (ally && target_enemy) || (enemy && target_ally);
+        if foe && transform.translation.distance(target_transform.translation) <= props.radius {
+            commands.entity(target).insert(Revealed {
+                remaining: props.duration,
+            });
+        }
+    }
+}
+
+fn revealed_system(mut commands: Commands, mut query: Query<(Entity, &mut Revealed)>) {
+    for (entity, mut revealed) in &mut query {
+        // Scans aren't affected by the dilation of whoever they spotted.
+        if revealed.remaining.tick(&TimeDilation::NONE) {
+            commands.entity(entity).remove::<Revealed>();
+        }
+    }
+}
+
+/// For a while, the user's rockets track the nearest foe, rather than their
+/// target.
+pub struct TargetingComputerAbility;
+impl AbilityDef for TargetingComputerAbility {
+    type Props = TargetingComputerProps;
+
+    const SLOTS: SlotKind = SlotKind::Head;
+
+    fn id() -> AbilityId {
+        AbilityId::from("targeting_computer")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(targeting_computer)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct TargetingComputerProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    duration: Dur,
+}
+
+impl Default for TargetingComputerProps {
+    fn default() -> Self {
+        Self {
+            cost: 20.0,
+            cooldown: Dur::new(900),
+            gcd: Dur::new(30),
+            duration: Dur::new(600),
+        }
+    }
+}
+
+impl Validate for TargetingComputerProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive_duration("duration", self.duration);
+    }
+}
+
+impl AbilityProps for TargetingComputerProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// An active targeting computer, on its user.
+///
+/// Note: The rockets themselves check for this; see `rocket::tracking_system`.
+#[derive(Component, Debug)]
+pub struct TargetingComputer {
+    remaining: Dur,
+}
+
+fn targeting_computer(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    props: Res<TargetingComputerProps>,
+) {
+    commands.entity(entity).insert(TargetingComputer {
+        remaining: props.duration,
+    });
+}
+
+fn targeting_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut TargetingComputer, &TimeDilation)>,
+) {
+    for (entity, mut computer, dilation) in &mut query {
+        if computer.remaining.tick(dilation) {
+            commands.entity(entity).remove::<TargetingComputer>();
+        }
+    }
+}
+
+/// For a while, the user regenerates energy faster, but heats up.
+pub struct OverclockAbility;
+impl AbilityDef for OverclockAbility {
+    type Props = OverclockProps;
+
+    const SLOTS: SlotKind = SlotKind::Head;
+
+    fn id() -> AbilityId {
+        AbilityId::from("overclock")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(overclock)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct OverclockProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    duration: Dur,
+    /// Extra energy regen per frame.
+    regen: f32,
+    /// Heat per frame.
+    heat: f32,
+}
+
+impl Default for OverclockProps {
+    fn default() -> Self {
+        Self {
+            cost: 0.0,
+            cooldown: Dur::new(900),
+            gcd: Dur::new(30),
+            duration: Dur::new(300),
+            regen: 0.3,
+            heat: 0.02,
+        }
+    }
+}
+
+impl Validate for OverclockProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive_duration("duration", self.duration);
+        v.non_negative("regen", self.regen);
+        v.non_negative("heat", self.heat);
+    }
+}
+
+impl AbilityProps for OverclockProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// An overclock in progress, on its user.
+#[derive(Component, Debug)]
+pub struct Overclock {
+    regen: f32,
+    heat: f32,
+    remaining: Dur,
+}
+
+fn overclock(In(entity): In<Entity>, mut commands: Commands, props: Res<OverclockProps>) {
+    commands.entity(entity).insert(Overclock {
+        regen: props.regen,
+        heat: props.heat,
+        remaining: props.duration,
+    });
+}
+
+fn overclock_system(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &mut Overclock,
+        &mut Energy,
+        &mut Temperature,
+        &TimeDilation,
+    )>,
+) {
+    for (entity, mut overclock, mut energy, mut temperature, dilation) in &mut query {
+        let factor = dilation.factor();
+        energy.cur = (energy.cur + overclock.regen * factor).min(energy.max);
+        temperature.heat(overclock.heat * factor);
+        if overclock.remaining.tick(dilation) {
+            commands.entity(entity).remove::<Overclock>();
+        }
+    }
+}
@@ -0,0 +1,240 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::With;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Vec3;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::linger::Linger;
+use super::linger::LingerPlugin;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::AbilityOffset;
+use crate::CharacterMarker;
+use crate::Health;
+use crate::Shootable;
+use crate::To2d;
+use crate::To3d;
+use crate::FORWARD;
+
+pub struct HealBeamPlugin;
+impl Plugin for HealBeamPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<HealBeamAbility>::default(),
+            LingerPlugin::<HealBeam>::default(),
+        ));
+    }
+}
+
+/// Locks on to the first character we aim at, and heals them for as long as
+/// it's held and we can see them.
+///
+/// Like all abilities, it doesn't care what team the target is on.
+pub struct HealBeamAbility;
+impl AbilityDef for HealBeamAbility {
+    type Props = HealBeamProps;
+
+    const SLOTS: SlotKind = SlotKind::Arm;
+
+    fn id() -> AbilityId {
+        AbilityId::from("heal_beam")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+
+    fn can_fire<S: Side>(world: &mut World) -> Option<SystemId<In<Entity>, bool>> {
+        Some(world.register_system(can_heal))
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct HealBeamProps {
+    /// Energy per frame.
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    range: f32,
+    /// Healing per frame.
+    heal: f32,
+}
+
+impl Default for HealBeamProps {
+    fn default() -> Self {
+        Self {
+            cost: 0.5,
+            cooldown: Dur::new(0),
+            gcd: Dur::new(0),
+            range: 4.0,
+            heal: 0.1,
+        }
+    }
+}
+
+impl Validate for HealBeamProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("range", self.range);
+        v.non_negative("heal", self.heal);
+    }
+}
+
+impl AbilityProps for HealBeamProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A heal beam, on the entity healing.
+#[derive(Component, Debug)]
+pub struct HealBeam {
+    pub target: Entity,
+}
+
+/// The first shootable thing from `origin` in `dir`, within `range`.
+///
+/// Note: We ignore `user`, as the ray starts inside its collider.
+fn first_hit(
+    rapier_context: &ReadDefaultRapierContext,
+    shootable_q: &Query<(), With<Shootable>>,
+    user: Entity,
+    origin: Vec3,
+    dir: Vec3,
+    range: f32,
+) -> Option<Entity> {
+    let pred = |e| e != user && shootable_q.get(e).is_ok();
+    let filter = QueryFilter::new().exclude_sensors().predicate(&pred);
+    rapier_context
+        .cast_ray(origin, dir, range, true, filter)
+        .map(|(entity, _)| entity)
+}
+
+/// Who a beam from `user` should be on: its current target, as long as we can
+/// still see them, or else the first valid target we're aiming at.
+///
+/// `position` gives the position of valid targets.
+pub(super) fn lock_on(
+    rapier_context: &ReadDefaultRapierContext,
+    shootable_q: &Query<(), With<Shootable>>,
+    user: Entity,
+    transform: &Transform,
+    origin: Vec3,
+    current: Option<Entity>,
+    range: f32,
+    position: impl Fn(Entity) -> Option<Vec3>,
+) -> Option<Entity> {
+    let locked = current.filter(|&target| {
+        let Some(target_position) = position(target) else {
+            return false;
+        };
+        let dir = (target_position.to_2d() - origin.to_2d())
+            .normalize_or_zero()
+            .to_3d(0.0);
+        first_hit(rapier_context, shootable_q, user, origin, dir, range) == Some(target)
+    });
+
+    locked.or_else(|| {
+        let dir = transform.rotation * FORWARD;
+        first_hit(rapier_context, shootable_q, user, origin, dir, range)
+            .filter(|&target| position(target).is_some())
+    })
+}
+
+/// Whether there's anyone to heal; healing no one shouldn't cost anything.
+fn can_heal(
+    In(entity): In<Entity>,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<(&Transform, &AbilityOffset, Option<&HealBeam>)>,
+    target_q: Query<&Transform, With<CharacterMarker>>,
+    shootable_q: Query<(), With<Shootable>>,
+    props: Res<HealBeamProps>,
+) -> bool {
+    let Ok((transform, ability_offset, beam)) = user_q.get(entity) else {
+        return false;
+    };
+    let origin = transform.translation + ability_offset.to_vec();
+
+    lock_on(
+        &rapier_context,
+        &shootable_q,
+        entity,
+        transform,
+        origin,
+        beam.map(|beam| beam.target),
+        props.range,
+        |target| target_q.get(target).ok().map(|t| t.translation),
+    )
+    .is_some()
+}
+
+fn fire(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<(&Transform, &AbilityOffset, Option<&HealBeam>)>,
+    mut target_q: Query<(&Transform, &mut Health, &TimeDilation), With<CharacterMarker>>,
+    shootable_q: Query<(), With<Shootable>>,
+    props: Res<HealBeamProps>,
+) {
+    let Ok((transform, ability_offset, beam)) = user_q.get(entity) else {
+        return;
+    };
+    let origin = transform.translation + ability_offset.to_vec();
+
+    let target = lock_on(
+        &rapier_context,
+        &shootable_q,
+        entity,
+        transform,
+        origin,
+        beam.map(|beam| beam.target),
+        props.range,
+        |target| target_q.get(target).ok().map(|(t, _, _)| t.translation),
+    );
+
+    let Some(target) = target else {
+        commands
+            .entity(entity)
+            .remove::<(HealBeam, Linger<HealBeam>)>();
+        return;
+    };
+
+    if let Ok((_, mut health, dilation)) = target_q.get_mut(target) {
+        health.take(-props.heal, dilation);
+    }
+
+    commands
+        .entity(entity)
+        .insert((HealBeam { target }, Linger::<HealBeam>::default()));
+}
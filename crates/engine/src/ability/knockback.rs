@@ -0,0 +1,403 @@
+use std::f32::consts::FRAC_PI_2;
+use std::f32::consts::TAU;
+
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::With;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Vec3;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::Ccd;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::ExternalForce;
+use bevy_rapier3d::prelude::LockedAxes;
+use bevy_rapier3d::prelude::ReadMassProperties;
+use bevy_rapier3d::prelude::RigidBody;
+use bevy_rapier3d::prelude::Sensor;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::cone::cone_targets;
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::collision::TrackCollisionBundle;
+use crate::collision::TrackCollisions;
+use crate::level::Floor;
+use crate::level::InLevel;
+use crate::lifecycle::Lifetime;
+use crate::movement::knockback_system;
+use crate::movement::Knockback;
+use crate::status_effect::StatusProps;
+use crate::time::Dur;
+use crate::AbilityOffset;
+use crate::CharacterMarker;
+use crate::GameSet;
+use crate::Health;
+use crate::MassBundle;
+use crate::Object;
+use crate::To2d;
+use crate::To3d;
+use crate::FORWARD;
+use crate::PLAYER_R;
+use crate::SCHEDULE;
+
+pub struct KnockbackPlugin;
+impl Plugin for KnockbackPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<HookAbility>::default(),
+            AbilityDefPlugin::<BlastAbility>::default(),
+        ))
+        .add_systems(
+            SCHEDULE,
+            (hook_collision_system, pull_system.before(knockback_system))
+                .chain()
+                .in_set(GameSet::Stuff),
+        );
+    }
+}
+
+/// Fires a grappling hook. It pulls characters it hits to the user, or the
+/// user to walls.
+pub struct HookAbility;
+impl AbilityDef for HookAbility {
+    type Props = HookProps;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        AbilityId::from("hook")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(hook)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct HookProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    radius: f32,
+    speed: f32,
+    /// How long the hook flies for; with `speed`, its range.
+    lifetime: Dur,
+    /// How hard the pull is, as an acceleration on a `PLAYER_MASS` target.
+    accel: f32,
+    /// The longest a pull can last.
+    duration: Dur,
+}
+
+impl Default for HookProps {
+    fn default() -> Self {
+        Self {
+            cost: 20.0,
+            cooldown: Dur::new(240),
+            gcd: Dur::new(30),
+            radius: 0.08,
+            speed: 8.0,
+            lifetime: Dur::new(30),
+            accel: 30.0,
+            duration: Dur::new(30),
+        }
+    }
+}
+
+impl Validate for HookProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("radius", self.radius);
+        v.positive("speed", self.speed);
+        v.positive_duration("lifetime", self.lifetime);
+        v.positive("accel", self.accel);
+        v.positive_duration("duration", self.duration);
+    }
+}
+
+impl AbilityProps for HookProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Hook {
+    pub user: Entity,
+    accel: f32,
+    duration: Dur,
+}
+
+fn hook(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<(&Transform, &AbilityOffset)>,
+    props: Res<HookProps>,
+) {
+    let Ok((transform, ability_offset)) = user_q.get(entity) else {
+        return;
+    };
+
+    let dir = transform.rotation * FORWARD;
+    let position =
+        transform.translation + dir * (PLAYER_R + props.radius) + ability_offset.to_vec();
+
+    commands.spawn((
+        Object {
+            transform: Transform::from_translation(position).with_scale(Vec3::splat(props.radius)),
+            collider: Collider::ball(1.0),
+            foot_offset: (-props.radius).into(),
+            mass: MassBundle::new(0.1),
+            body: RigidBody::Dynamic,
+            force: ExternalForce::default(),
+            velocity: Velocity::linear(dir * props.speed),
+            locked_axes: LockedAxes::ROTATION_LOCKED | LockedAxes::TRANSLATION_LOCKED_Y,
+            in_level: InLevel,
+            statuses: StatusProps {
+                thermal_mass: 1.0,
+                capacitance: 1.0,
+            }
+            .into(),
+            collisions: TrackCollisionBundle::on(),
+        },
+        Lifetime::new(props.lifetime),
+        Sensor,
+        Ccd::enabled(),
+        Health::new(1.0),
+        Hook {
+            user: entity,
+            accel: props.accel,
+            duration: props.duration,
+        },
+    ));
+}
+
+/// What a pull drags something toward.
+#[derive(Debug, Copy, Clone)]
+enum Anchor {
+    Entity(Entity),
+    Point(Vec3),
+}
+
+/// Something being pulled by a hook.
+///
+/// Note: The pulling itself is a `Knockback`, which this keeps pointed at the
+/// anchor.
+#[derive(Component, Debug)]
+pub struct HookPull {
+    anchor: Anchor,
+    accel: f32,
+}
+
+/// How close a pull gets something before letting go.
+const PULL_STOP: f32 = PLAYER_R * 3.0;
+
+fn hook_collision_system(
+    mut commands: Commands,
+    mut hook_q: Query<(&Hook, &Transform, &TrackCollisions, &mut Health)>,
+    character_q: Query<(), With<CharacterMarker>>,
+    wall_q: Query<(), With<Floor>>,
+) {
+    for (hook, transform, colliding, mut health) in &mut hook_q {
+        for &target in &colliding.targets {
+            if target == hook.user {
+                continue;
+            }
+            let (pulled, anchor) = if character_q.contains(target) {
+                (target, Anchor::Entity(hook.user))
+            } else if wall_q.contains(target) {
+                (hook.user, Anchor::Point(transform.translation))
+            } else {
+                continue;
+            };
+
+            commands.entity(pulled).insert((
+                HookPull {
+                    anchor,
+                    accel: hook.accel,
+                },
+                Knockback {
+                    force: Vec3::ZERO,
+                    remaining: hook.duration,
+                },
+            ));
+            health.die();
+            break;
+        }
+    }
+}
+
+fn pull_system(
+    mut commands: Commands,
+    mut pulled_q: Query<(
+        Entity,
+        &HookPull,
+        &Transform,
+        &ReadMassProperties,
+        Option<&mut Knockback>,
+    )>,
+    anchor_q: Query<&Transform>,
+) {
+    for (entity, pull, transform, mass, knockback) in &mut pulled_q {
+        let anchor = match pull.anchor {
+            Anchor::Entity(anchor) => anchor_q.get(anchor).ok().map(|t| t.translation),
+            Anchor::Point(point) => Some(point),
+        };
+        let delta = anchor.map(|a| a.to_2d() - transform.translation.to_2d());
+
+        match (delta, knockback) {
+            (Some(delta), Some(mut knockback)) if delta.length() > PULL_STOP => {
+                knockback.force =
+                    delta.normalize().to_3d(0.0) * Knockback::force(pull.accel, mass.mass);
+            }
+            _ => {
+                commands
+                    .entity(entity)
+                    .remove::<HookPull>()
+                    .remove::<Knockback>();
+            }
+        }
+    }
+}
+
+/// Shoves everything in a cone in front of the user away.
+pub struct BlastAbility;
+impl AbilityDef for BlastAbility {
+    type Props = BlastProps;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        AbilityId::from("concussive_blast")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(blast)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct BlastProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    range: f32,
+    /// The full angle of the cone, in radians.
+    arc: f32,
+    /// How hard the shove is, as an acceleration on a `PLAYER_MASS` target.
+    accel: f32,
+    duration: Dur,
+}
+
+impl Default for BlastProps {
+    fn default() -> Self {
+        Self {
+            cost: 25.0,
+            cooldown: Dur::new(180),
+            gcd: Dur::new(30),
+            range: 2.5,
+            arc: FRAC_PI_2,
+            accel: 40.0,
+            duration: Dur::new(8),
+        }
+    }
+}
+
+impl Validate for BlastProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("range", self.range);
+        v.check(
+            "arc",
+            self.arc > 0.0 && self.arc <= TAU,
+            "must be between 0 and 2π",
+        );
+        v.positive("accel", self.accel);
+        v.positive_duration("duration", self.duration);
+    }
+}
+
+impl AbilityProps for BlastProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+fn blast(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<&AbilityOffset>,
+    target_q: Query<&ReadMassProperties, With<ExternalForce>>,
+    transform_q: Query<&Transform>,
+    wall_q: Query<(), With<Floor>>,
+    props: Res<BlastProps>,
+) {
+    let (Ok(transform), Ok(ability_offset)) = (transform_q.get(entity), user_q.get(entity)) else {
+        return;
+    };
+
+    let origin = transform.translation + ability_offset.to_vec();
+    let facing = transform.rotation * FORWARD;
+    let targets = cone_targets(
+        &rapier_context,
+        entity,
+        origin,
+        props.range,
+        props.arc,
+        &transform_q,
+        &wall_q,
+    );
+    for target in targets {
+        let (Ok(mass), Ok(target_transform)) = (target_q.get(target), transform_q.get(target))
+        else {
+            continue;
+        };
+        if !mass.mass.is_normal() {
+            continue;
+        }
+
+        let dir = target_transform.translation.to_2d() - origin.to_2d();
+        let dir = dir.try_normalize().unwrap_or(facing.to_2d());
+        commands.entity(target).insert(Knockback {
+            force: dir.to_3d(0.0) * Knockback::force(props.accel, mass.mass),
+            remaining: props.duration,
+        });
+    }
+}
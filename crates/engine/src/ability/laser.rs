@@ -0,0 +1,167 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::EventWriter;
+use bevy_ecs::query::With;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::linger::Linger;
+use super::linger::LingerPlugin;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::status_effect::Temperature;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::AbilityOffset;
+use crate::DamageEvent;
+use crate::Health;
+use crate::Shootable;
+use crate::FORWARD;
+
+pub struct LaserPlugin;
+impl Plugin for LaserPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<LaserAbility>::default(),
+            LingerPlugin::<LaserBeam>::default(),
+        ));
+    }
+}
+
+/// A beam that hits the first thing in front of it, for as long as it's held.
+///
+/// Its cost is paid every frame it fires, so it drains energy while channeled.
+pub struct LaserAbility;
+impl AbilityDef for LaserAbility {
+    type Props = LaserProps;
+
+    const SLOTS: SlotKind = SlotKind::Arm;
+
+    fn id() -> AbilityId {
+        AbilityId::from("laser")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct LaserProps {
+    /// Energy per frame.
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    range: f32,
+    /// Damage per frame.
+    damage: f32,
+    /// Heat per frame; negative to cool.
+    heat: f32,
+}
+
+impl Default for LaserProps {
+    fn default() -> Self {
+        Self {
+            cost: 0.6,
+            cooldown: Dur::new(0),
+            gcd: Dur::new(0),
+            range: 10.0,
+            damage: 0.15,
+            heat: 0.0,
+        }
+    }
+}
+
+impl Validate for LaserProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("range", self.range);
+        v.finite("damage", self.damage);
+        v.finite("heat", self.heat);
+    }
+}
+
+impl AbilityProps for LaserProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A laser beam, on the entity firing it.
+///
+/// It starts at the user's `AbilityOffset` and goes straight forward.
+#[derive(Component, Debug)]
+pub struct LaserBeam {
+    pub length: f32,
+}
+
+fn fire(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<(&Transform, &AbilityOffset)>,
+    mut target_q: Query<(&mut Health, Option<&mut Temperature>, &TimeDilation)>,
+    shootable_q: Query<(), With<Shootable>>,
+    mut damage_events: EventWriter<DamageEvent>,
+    props: Res<LaserProps>,
+) {
+    let Ok((transform, ability_offset)) = user_q.get(entity) else {
+        return;
+    };
+
+    // Only shootable things stop the beam; that includes walls. We ignore
+    // ourselves, as the ray starts inside our collider.
+    let pred = |e| e != entity && shootable_q.get(e).is_ok();
+    let filter = QueryFilter::new().exclude_sensors().predicate(&pred);
+    let origin = transform.translation + ability_offset.to_vec();
+    let dir = transform.rotation * FORWARD;
+
+    let length = match rapier_context.cast_ray(origin, dir, props.range, true, filter) {
+        Some((target, toi)) => {
+            if let Ok((mut health, temperature, dilation)) = target_q.get_mut(target) {
+                let amount = health.take(props.damage, dilation);
+                if let Some(mut temperature) = temperature {
+                    temperature.heat(props.heat * dilation.factor());
+                }
+                damage_events.send(DamageEvent {
+                    source: entity,
+                    target,
+                    amount,
+                });
+            }
+            toi
+        }
+        None => props.range,
+    };
+
+    commands
+        .entity(entity)
+        .insert((LaserBeam { length }, Linger::<LaserBeam>::default()));
+}
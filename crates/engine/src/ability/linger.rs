@@ -0,0 +1,67 @@
+//! Components that stay around for a moment after their ability stops
+//! firing, so that things fired every frame don't flicker.
+
+use std::marker::PhantomData;
+
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::Query;
+
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::GameSet;
+use crate::SCHEDULE;
+
+/// How many frames a component lingers after it was last inserted.
+const LINGER: u32 = 2;
+
+/// Removes `T` from anything whose `Linger<T>` has run out.
+pub struct LingerPlugin<T: Component> {
+    _marker: PhantomData<T>,
+}
+
+impl<T: Component> Default for LingerPlugin<T> {
+    fn default() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Component> Plugin for LingerPlugin<T> {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(SCHEDULE, linger_system::<T>.in_set(GameSet::Stuff));
+    }
+}
+
+/// Keeps `T` on its entity until `LINGER` frames after it was last inserted.
+///
+/// Insert it alongside `T` each frame the ability fires.
+#[derive(Component, Debug)]
+pub struct Linger<T: Component> {
+    remaining: Dur,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Component> Default for Linger<T> {
+    fn default() -> Self {
+        Self {
+            remaining: Dur::new(LINGER),
+            _marker: PhantomData,
+        }
+    }
+}
+
+fn linger_system<T: Component>(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Linger<T>, &TimeDilation)>,
+) {
+    for (entity, mut linger, dilation) in &mut query {
+        if linger.remaining.tick(dilation) {
+            commands.entity(entity).remove::<(T, Linger<T>)>();
+        }
+    }
+}
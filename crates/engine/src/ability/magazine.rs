@@ -0,0 +1,323 @@
+//! Magazines, for abilities that shoot rounds rather than (or as well as)
+//! spending energy.
+//!
+//! An ability adopts one by returning its `MagazineProps` from
+//! `AbilityProps::magazine`; firing then takes a round, and reloading costs the
+//! same energy no matter how many rounds are left.
+
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::EntityCommands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::world::EntityWorldMut;
+use bevy_utils::HashMap;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::cooldown::Cooldown;
+use super::def::slot;
+use super::def::AbilityDef;
+use super::props::Validate;
+use super::props::Validator;
+use super::Side;
+use super::Slot;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::Energy;
+use crate::GameSet;
+use crate::SCHEDULE;
+
+pub struct MagazinePlugin;
+impl Plugin for MagazinePlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_systems(SCHEDULE, magazine_system.in_set(GameSet::Reset));
+    }
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize)]
+pub struct MagazineProps {
+    /// How many rounds a full magazine holds.
+    pub size: u32,
+    /// How long reloading takes; we can't fire in the meantime.
+    pub reload_time: Dur,
+    /// What reloading costs, however many rounds are left.
+    pub reload_cost: f32,
+    /// How long until any ability can be used, after starting a reload.
+    pub reload_gcd: Dur,
+    /// Spare rounds to reload from; `None` for as many as we like.
+    #[serde(default)]
+    pub reserve: Option<u32>,
+    /// Whether to start reloading on our own, as soon as we're empty.
+    #[serde(default)]
+    pub auto_reload: bool,
+}
+
+impl Validate for MagazineProps {
+    fn validate(&self, v: &mut Validator) {
+        v.check("size", self.size > 0, "must be positive");
+        v.duration("reload_time", self.reload_time);
+        v.non_negative("reload_cost", self.reload_cost);
+        v.duration("reload_gcd", self.reload_gcd);
+    }
+}
+
+/// One ability's magazine.
+#[derive(Debug, Clone)]
+pub struct Magazine {
+    props: MagazineProps,
+    rounds: u32,
+    reserve: Option<u32>,
+    /// Time left on a reload in progress.
+    reloading: Option<Dur>,
+}
+
+impl Magazine {
+    pub fn new(props: &MagazineProps) -> Self {
+        Self {
+            props: *props,
+            rounds: props.size,
+            reserve: props.reserve,
+            reloading: None,
+        }
+    }
+
+    pub fn rounds(&self) -> u32 {
+        self.rounds
+    }
+
+    pub fn size(&self) -> u32 {
+        self.props.size
+    }
+
+    /// Spare rounds left, or `None` if there's no limit.
+    pub fn reserve(&self) -> Option<u32> {
+        self.reserve
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rounds == 0
+    }
+
+    pub fn is_reloading(&self) -> bool {
+        self.reloading.is_some()
+    }
+
+    /// Whether there's a round ready to fire.
+    pub fn can_fire(&self) -> bool {
+        !self.is_empty() && !self.is_reloading()
+    }
+
+    fn can_reload(&self) -> bool {
+        !self.is_reloading() && self.rounds < self.props.size && self.reserve != Some(0)
+    }
+
+    /// Takes a round, if we can fire.
+    pub fn try_use(&mut self) -> bool {
+        if self.can_fire() {
+            self.rounds -= 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Starts reloading, if there's any point and we can pay for it.
+    pub fn try_reload(&mut self, energy: &mut Energy) -> bool {
+        if self.can_reload() && energy.try_use(self.props.reload_cost) {
+            self.reloading = Some(self.props.reload_time);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn tick(&mut self, energy: &mut Energy, dilation: &TimeDilation) {
+        if self.props.auto_reload && self.is_empty() {
+            self.try_reload(energy);
+        }
+
+        let Some(remaining) = &mut self.reloading else {
+            return;
+        };
+        if !remaining.tick(dilation) {
+            return;
+        }
+        self.reloading = None;
+
+        let wanted = self.props.size - self.rounds;
+        let loaded = match &mut self.reserve {
+            Some(reserve) => {
+                let loaded = wanted.min(*reserve);
+                *reserve -= loaded;
+                loaded
+            }
+            None => wanted,
+        };
+        self.rounds += loaded;
+    }
+}
+
+/// The magazines of everything a character has equipped, by slot.
+#[derive(Component, Debug, Default)]
+pub struct Magazines(HashMap<Slot, Magazine>);
+
+impl Magazines {
+    pub fn get(&self, slot: Slot) -> Option<&Magazine> {
+        self.0.get(&slot)
+    }
+
+    pub fn get_mut(&mut self, slot: Slot) -> Option<&mut Magazine> {
+        self.0.get_mut(&slot)
+    }
+}
+
+/// Gives the user a magazine for the ability in `slot`.
+pub(super) fn add_magazine(entity: &mut EntityCommands, slot: Slot, props: &MagazineProps) {
+    let magazine = Magazine::new(props);
+    entity.queue(
+        move |mut entity: EntityWorldMut| match entity.get_mut::<Magazines>() {
+            Some(mut magazines) => {
+                magazines.0.insert(slot, magazine);
+            }
+            None => {
+                entity.insert(Magazines([(slot, magazine)].into_iter().collect()));
+            }
+        },
+    );
+}
+
+/// A secondary action that reloads; for arm abilities with a magazine.
+pub fn reload<A: AbilityDef, S: Side>(
+    In(entity): In<Entity>,
+    mut user_q: Query<(&mut Magazines, &mut Energy, &mut Cooldown, &TimeDilation)>,
+) {
+    let Ok((mut magazines, mut energy, mut gcd, time_dilation)) = user_q.get_mut(entity) else {
+        return;
+    };
+    let Some(magazine) = magazines.get_mut(slot::<A, S>()) else {
+        return;
+    };
+
+    if !gcd.is_available(time_dilation) {
+        return;
+    }
+
+    if magazine.try_reload(&mut energy) {
+        gcd.set(magazine.props.reload_gcd);
+    }
+}
+
+fn magazine_system(mut query: Query<(&mut Magazines, &mut Energy, &TimeDilation)>) {
+    for (mut magazines, mut energy, dilation) in &mut query {
+        for magazine in magazines.0.values_mut() {
+            magazine.tick(&mut energy, dilation);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Magazine;
+    use super::MagazineProps;
+    use crate::status_effect::TimeDilation;
+    use crate::time::Dur;
+    use crate::Energy;
+
+    fn props() -> MagazineProps {
+        MagazineProps {
+            size: 3,
+            reload_time: Dur::new(2),
+            reload_cost: 10.0,
+            reload_gcd: Dur::new(0),
+            reserve: None,
+            auto_reload: false,
+        }
+    }
+
+    #[test]
+    fn uses_rounds_until_empty() {
+        let mut magazine = Magazine::new(&props());
+        assert!(magazine.try_use());
+        assert!(magazine.try_use());
+        assert!(magazine.try_use());
+        assert!(magazine.is_empty());
+        assert!(!magazine.try_use());
+    }
+
+    #[test]
+    fn reload_costs_the_same_however_many_remain() {
+        let mut energy = Energy::new(100.0, 0.0);
+
+        // Nothing to reload on a full magazine, so it's free.
+        let mut magazine = Magazine::new(&props());
+        assert!(!magazine.try_reload(&mut energy));
+        assert_eq!(energy.cur, 100.0);
+
+        magazine.try_use();
+        assert!(magazine.try_reload(&mut energy));
+        assert_eq!(energy.cur, 90.0);
+        // Already reloading.
+        assert!(!magazine.try_reload(&mut energy));
+        assert!(!magazine.can_fire());
+
+        let mut magazine = Magazine::new(&props());
+        for _ in 0..3 {
+            magazine.try_use();
+        }
+        assert!(magazine.try_reload(&mut energy));
+        assert_eq!(energy.cur, 80.0);
+
+        let mut broke = Energy::new(5.0, 0.0);
+        assert!(!magazine.clone().try_reload(&mut broke));
+    }
+
+    #[test]
+    fn tick_finishes_reloads_from_reserve() {
+        let mut energy = Energy::new(100.0, 0.0);
+        let dilation = TimeDilation::default();
+        let mut magazine = Magazine::new(&MagazineProps {
+            reserve: Some(2),
+            ..props()
+        });
+        for _ in 0..3 {
+            magazine.try_use();
+        }
+
+        magazine.try_reload(&mut energy);
+        magazine.tick(&mut energy, &dilation);
+        assert!(magazine.is_reloading());
+        magazine.tick(&mut energy, &dilation);
+        assert!(!magazine.is_reloading());
+        // We only had two spare rounds.
+        assert_eq!(magazine.rounds(), 2);
+        assert_eq!(magazine.reserve(), Some(0));
+
+        for _ in 0..2 {
+            magazine.try_use();
+        }
+        assert!(!magazine.try_reload(&mut energy));
+    }
+
+    #[test]
+    fn tick_auto_reloads_when_empty() {
+        let mut energy = Energy::new(100.0, 0.0);
+        let dilation = TimeDilation::default();
+        let mut magazine = Magazine::new(&MagazineProps {
+            auto_reload: true,
+            ..props()
+        });
+
+        magazine.try_use();
+        magazine.tick(&mut energy, &dilation);
+        assert!(!magazine.is_reloading());
+
+        magazine.try_use();
+        magazine.try_use();
+        magazine.tick(&mut energy, &dilation);
+        assert!(magazine.is_reloading());
+        assert_eq!(energy.cur, 90.0);
+    }
+}
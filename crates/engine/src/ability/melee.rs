@@ -0,0 +1,272 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::EventWriter;
+use bevy_ecs::query::With;
+use bevy_ecs::query::Without;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::ExternalForce;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::bullet::Bullet;
+use super::cone::sector;
+use super::cooldown::Cooldown;
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::Resources;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::collision::sorted_intersections;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::AbilityOffset;
+use crate::DamageEvent;
+use crate::Energy;
+use crate::GameSet;
+use crate::Health;
+use crate::To2d;
+use crate::To3d;
+use crate::PLAYER_HEIGHT;
+use crate::PLAYER_R;
+use crate::SCHEDULE;
+
+pub struct MeleePlugin;
+impl Plugin for MeleePlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins(AbilityDefPlugin::<MeleeAbility>::default())
+            .add_systems(SCHEDULE, swing_system.in_set(GameSet::Stuff));
+    }
+}
+
+pub struct MeleeAbility;
+impl AbilityDef for MeleeAbility {
+    type Props = MeleeProps;
+
+    const SLOTS: SlotKind = SlotKind::Arm;
+
+    fn id() -> AbilityId {
+        AbilityId::from("melee")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+
+    fn secondary<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(parry::<S>)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct MeleeProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    /// How far past the user's edge a swing reaches.
+    range: f32,
+    /// The full angle of the swing, in radians.
+    arc: f32,
+    damage: f32,
+    force: f32,
+    /// How long a swing takes to draw; it hits immediately.
+    swing_duration: Dur,
+    parry_cost: f32,
+    parry_cooldown: Dur,
+    parry_gcd: Dur,
+}
+
+impl Default for MeleeProps {
+    fn default() -> Self {
+        Self {
+            cost: 5.0,
+            cooldown: Dur::new(30),
+            gcd: Dur::new(0),
+            range: 0.5,
+            arc: std::f32::consts::FRAC_PI_2,
+            damage: 8.0,
+            force: 1500.0,
+            swing_duration: Dur::new(12),
+            parry_cost: 20.0,
+            parry_cooldown: Dur::new(60),
+            parry_gcd: Dur::new(15),
+        }
+    }
+}
+
+impl Validate for MeleeProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("range", self.range);
+        v.check(
+            "arc",
+            self.arc > 0.0 && self.arc <= std::f32::consts::TAU,
+            "must be between 0 and 2π",
+        );
+        v.finite("damage", self.damage);
+        v.finite("force", self.force);
+        v.positive_duration("swing_duration", self.swing_duration);
+        v.non_negative("parry_cost", self.parry_cost);
+        v.duration("parry_cooldown", self.parry_cooldown);
+        v.duration("parry_gcd", self.parry_gcd);
+    }
+}
+
+impl AbilityProps for MeleeProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A swing in progress, on the entity swinging.
+///
+/// The hit happens when the swing starts; this is just so we can draw it.
+#[derive(Component, Debug)]
+pub struct Swing {
+    pub range: f32,
+    pub arc: f32,
+    pub duration: Dur,
+    pub remaining: Dur,
+    pub parry: bool,
+}
+
+impl Swing {
+    fn new(props: &MeleeProps, parry: bool) -> Self {
+        Self {
+            range: props.range,
+            arc: props.arc,
+            duration: props.swing_duration,
+            remaining: props.swing_duration,
+            parry,
+        }
+    }
+
+    /// How far through the swing we are, from 0 to 1.
+    pub fn fraction(&self) -> f32 {
+        1.0 - self.remaining / self.duration
+    }
+}
+
+/// Everything in the arc of a swing by `user`, in a consistent order.
+fn in_reach(
+    rapier_context: &ReadDefaultRapierContext,
+    user: Entity,
+    transform: &Transform,
+    ability_offset: &AbilityOffset,
+    props: &MeleeProps,
+) -> Vec<Entity> {
+    let origin = transform.translation + ability_offset.to_vec();
+    let shape = sector(PLAYER_R + props.range, props.arc, PLAYER_HEIGHT);
+    let filter = QueryFilter::new().exclude_collider(user);
+    sorted_intersections(rapier_context, origin, transform.rotation, &shape, filter)
+}
+
+fn fire(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<(&Transform, &AbilityOffset, &TimeDilation)>,
+    mut target_q: Query<
+        (&Transform, &mut Health, &mut ExternalForce, &TimeDilation),
+        Without<Bullet>,
+    >,
+    mut damage_events: EventWriter<DamageEvent>,
+    props: Res<MeleeProps>,
+) {
+    let Ok((transform, ability_offset, dilation)) = user_q.get(entity) else {
+        return;
+    };
+
+    for target in in_reach(&rapier_context, entity, transform, ability_offset, &props) {
+        let Ok((target_transform, mut health, mut force, target_dilation)) =
+            target_q.get_mut(target)
+        else {
+            continue;
+        };
+        let amount = health.take(props.damage, target_dilation);
+        damage_events.send(DamageEvent {
+            source: entity,
+            target,
+            amount,
+        });
+        let dir = (target_transform.translation.to_2d() - transform.translation.to_2d())
+            .normalize_or_zero()
+            .to_3d(0.0);
+        force.force += dir * props.force * dilation.factor();
+    }
+
+    commands.entity(entity).insert(Swing::new(&props, false));
+}
+
+fn parry<S: Side>(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    mut user_q: Query<(
+        &Transform,
+        &AbilityOffset,
+        &mut Cooldown,
+        &mut Energy,
+        &mut Resources<MeleeAbility, S>,
+        &TimeDilation,
+    )>,
+    mut bullet_q: Query<&mut Health, With<Bullet>>,
+    props: Res<MeleeProps>,
+) {
+    let Ok((transform, ability_offset, mut gcd, mut energy, mut resources, dilation)) =
+        user_q.get_mut(entity)
+    else {
+        return;
+    };
+
+    if !gcd.is_available(dilation) || !resources.cooldown.is_available(dilation) {
+        return;
+    }
+
+    if !energy.try_use(props.parry_cost) {
+        return;
+    }
+
+    gcd.set(props.parry_gcd);
+    resources.cooldown.set(props.parry_cooldown);
+
+    for target in in_reach(&rapier_context, entity, transform, ability_offset, &props) {
+        if let Ok(mut health) = bullet_q.get_mut(target) {
+            health.die();
+        }
+    }
+
+    commands.entity(entity).insert(Swing::new(&props, true));
+}
+
+fn swing_system(mut commands: Commands, mut query: Query<(Entity, &mut Swing, &TimeDilation)>) {
+    for (entity, mut swing, dilation) in &mut query {
+        if swing.remaining.tick(dilation) {
+            commands.entity(entity).remove::<Swing>();
+        }
+    }
+}
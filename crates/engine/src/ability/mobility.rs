@@ -0,0 +1,275 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Quat;
+use bevy_math::Vec3;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::FootOffset;
+use crate::GameSet;
+use crate::Target;
+use crate::To2d;
+use crate::To3d;
+use crate::SCHEDULE;
+
+pub struct MobilityPlugin;
+impl Plugin for MobilityPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<BlinkAbility>::default(),
+            AbilityDefPlugin::<JumpAbility>::default(),
+        ))
+        .add_systems(SCHEDULE, airborne_system.in_set(GameSet::Stuff));
+    }
+}
+
+/// Teleports the user to their target, up to a maximum range.
+pub struct BlinkAbility;
+impl AbilityDef for BlinkAbility {
+    type Props = BlinkProps;
+
+    const SLOTS: SlotKind = SlotKind::Legs;
+
+    fn id() -> AbilityId {
+        AbilityId::from("blink")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(blink)
+    }
+
+    fn can_fire<S: Side>(world: &mut World) -> Option<SystemId<In<Entity>, bool>> {
+        Some(world.register_system(can_blink))
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct BlinkProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    range: f32,
+}
+
+impl Default for BlinkProps {
+    fn default() -> Self {
+        Self {
+            cost: 20.0,
+            cooldown: Dur::new(120),
+            gcd: Dur::new(15),
+            range: 3.0,
+        }
+    }
+}
+
+impl Validate for BlinkProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("range", self.range);
+    }
+}
+
+impl AbilityProps for BlinkProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// How far above someone's feet we look for something in the way, so that
+/// the floor they're standing on doesn't count.
+const CLEARANCE: f32 = 0.05;
+
+/// How far below someone's feet there needs to be floor.
+const GROUND_DEPTH: f32 = 0.2;
+
+/// Where a blink from `transform` toward `target` lands.
+fn blink_destination(transform: &Transform, target: &Target, props: &BlinkProps) -> Vec3 {
+    let start = transform.translation.to_2d();
+    let end = start + (target.0 - start).clamp_length_max(props.range);
+    end.to_3d(transform.translation.y)
+}
+
+/// Whether the user has somewhere to land; a refused blink shouldn't cost
+/// anything.
+fn can_blink(
+    In(entity): In<Entity>,
+    rapier_context: ReadDefaultRapierContext,
+    user_q: Query<(&Transform, &Target, &FootOffset, &Collider)>,
+    props: Res<BlinkProps>,
+) -> bool {
+    let Ok((transform, target, foot_offset, collider)) = user_q.get(entity) else {
+        return false;
+    };
+
+    let destination = blink_destination(transform, target, &props);
+    let feet = destination.y + foot_offset.y;
+
+    let filter = QueryFilter::new()
+        .exclude_sensors()
+        .exclude_collider(entity);
+    let mut blocked = false;
+    rapier_context.intersections_with_shape(
+        destination + Vec3::Y * CLEARANCE,
+        Quat::IDENTITY,
+        collider,
+        filter,
+        |_| {
+            blocked = true;
+            false
+        },
+    );
+    let grounded = rapier_context
+        .cast_ray(
+            destination.with_y(feet + CLEARANCE),
+            -Vec3::Y,
+            CLEARANCE + GROUND_DEPTH,
+            true,
+            filter,
+        )
+        .is_some();
+
+    !blocked && grounded
+}
+
+fn blink(
+    In(entity): In<Entity>,
+    mut user_q: Query<(&mut Transform, &Target)>,
+    props: Res<BlinkProps>,
+) {
+    let Ok((mut transform, target)) = user_q.get_mut(entity) else {
+        return;
+    };
+
+    transform.translation = blink_destination(&transform, target, &props);
+}
+
+/// Briefly lifts the user, letting them clear short walls and dodge bullets.
+pub struct JumpAbility;
+impl AbilityDef for JumpAbility {
+    type Props = JumpProps;
+
+    const SLOTS: SlotKind = SlotKind::Legs;
+
+    fn id() -> AbilityId {
+        AbilityId::from("jump")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(jump)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct JumpProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    /// Vertical speed at takeoff; enough to clear a `SHORT_WALL`.
+    speed: f32,
+    /// How long we count as airborne.
+    duration: Dur,
+}
+
+impl Default for JumpProps {
+    fn default() -> Self {
+        Self {
+            cost: 15.0,
+            cooldown: Dur::new(90),
+            gcd: Dur::new(15),
+            speed: 4.0,
+            duration: Dur::new(50),
+        }
+    }
+}
+
+impl Validate for JumpProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("speed", self.speed);
+        v.positive_duration("duration", self.duration);
+    }
+}
+
+impl AbilityProps for JumpProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// Someone in the middle of a jump. Bullets, which skim the ground, pass
+/// under them.
+#[derive(Component, Debug)]
+pub struct Airborne {
+    remaining: Dur,
+}
+
+fn jump(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    mut user_q: Query<&mut Velocity>,
+    props: Res<JumpProps>,
+) {
+    let Ok(mut velocity) = user_q.get_mut(entity) else {
+        return;
+    };
+
+    velocity.linvel.y = props.speed;
+    commands.entity(entity).insert(Airborne {
+        remaining: props.duration,
+    });
+}
+
+fn airborne_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Airborne, &TimeDilation)>,
+) {
+    for (entity, mut airborne, dilation) in &mut query {
+        if airborne.remaining.tick(dilation) {
+            commands.entity(entity).remove::<Airborne>();
+        }
+    }
+}
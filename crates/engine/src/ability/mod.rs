@@ -1,3 +1,5 @@
+use std::collections::BTreeSet;
+
 use bevy_app::Plugin;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::system::In;
@@ -8,50 +10,118 @@ use bevy_ecs::world::FromWorld;
 use bevy_ecs::world::World;
 use bevy_reflect::TypePath;
 use bevy_utils::HashMap;
+use capacitor_field::CapacitorFieldPlugin;
+use cone::ConePlugin;
+use dilation_field::DilationFieldPlugin;
+use energy_beam::EnergyBeamPlugin;
 use explosion::ExplosionPlugin;
 use gravity_ball::GravityBallPlugin;
 use grenade::GrenadePlugin;
 use gun::GunPlugin;
+use head::HeadPlugin;
+use heal_beam::HealBeamPlugin;
+use knockback::KnockbackPlugin;
+use laser::LaserPlugin;
+use magazine::MagazinePlugin;
+use melee::MeleePlugin;
+use mobility::MobilityPlugin;
+use passive::PassivePlugin;
+use projectile::ProjectilePlugin;
+use props::PropsPlugin;
 use rocket::RocketPlugin;
 use serde::Deserialize;
 use serde::Serialize;
+use shield::ShieldPlugin;
 use subenum::subenum;
 use transport::TransportBeamPlugin;
+use tree::NodeId;
+use tree::SkillTrees;
+use turret::TurretPlugin;
+
+use crate::multiplayer::Action;
 
 pub mod bullet;
+pub mod capacitor_field;
+pub mod cone;
 pub mod cooldown;
+pub mod def;
+pub mod dilation_field;
+pub mod energy_beam;
 pub mod explosion;
 pub mod gravity_ball;
 pub mod grenade;
 pub mod gun;
+pub mod head;
+pub mod heal_beam;
+pub mod knockback;
+pub mod laser;
+pub mod linger;
+pub mod magazine;
+pub mod melee;
+pub mod mobility;
+pub mod passive;
+pub mod projectile;
+pub mod props;
 pub mod rocket;
+pub mod shield;
 pub mod transport;
+pub mod tree;
+pub mod turret;
 
 pub struct AbilityPlugin;
 impl Plugin for AbilityPlugin {
     fn build(&self, app: &mut bevy_app::App) {
+        app.init_resource::<SkillTrees>();
+        // Bevy only takes so many plugins in one tuple.
         app.add_plugins((
+            CapacitorFieldPlugin,
+            ConePlugin,
+            DilationFieldPlugin,
+            EnergyBeamPlugin,
             ExplosionPlugin,
             GravityBallPlugin,
             GrenadePlugin,
             GunPlugin,
+            HeadPlugin,
+            HealBeamPlugin,
+        ))
+        .add_plugins((
+            KnockbackPlugin,
+            LaserPlugin,
+            MagazinePlugin,
+            MeleePlugin,
+            MobilityPlugin,
+            PassivePlugin,
+            ProjectilePlugin,
             RocketPlugin,
+            ShieldPlugin,
             TransportBeamPlugin,
-        ));
+            TurretPlugin,
+        ))
+        // This needs to come last, to replace the built-in props.
+        .add_plugins(PropsPlugin::default());
     }
 }
 
-pub trait Side: Default + Send + Sync + Clone + Copy + 'static {}
+pub trait Side: Default + Send + Sync + Clone + Copy + 'static {
+    const SIDE: SideEnum;
+}
 #[derive(Debug, Copy, Clone, Default, TypePath)]
 pub struct Left;
 #[derive(Debug, Copy, Clone, Default, TypePath)]
 pub struct Right;
 
-impl Side for Left {}
-impl Side for Right {}
+impl Side for Left {
+    const SIDE: SideEnum = SideEnum::Left;
+}
+impl Side for Right {
+    const SIDE: SideEnum = SideEnum::Right;
+}
 
 fn noop_ability(_: In<Entity>) {}
 
+fn noop_setup(_: In<(Entity, BTreeSet<NodeId>)>) {}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum SideEnum {
     Left,
@@ -69,12 +139,34 @@ pub enum Slot {
     Legs,
     #[subenum(NonArmSlot)]
     Head,
+    /// Passives, which are always on.
+    #[subenum(NonArmSlot)]
+    Body,
+}
+
+impl Slot {
+    /// The button that uses this slot, if it has one.
+    pub fn action(self) -> Option<Action> {
+        let action = match self {
+            Slot::Arm(SideEnum::Left) => Action::LeftArm,
+            Slot::Arm(SideEnum::Right) => Action::RightArm,
+            Slot::ArmSecondary(SideEnum::Left) => Action::LeftArmSecondary,
+            Slot::ArmSecondary(SideEnum::Right) => Action::RightArmSecondary,
+            Slot::Shoulder(SideEnum::Left) => Action::LeftShoulder,
+            Slot::Shoulder(SideEnum::Right) => Action::RightShoulder,
+            Slot::Legs => Action::Legs,
+            Slot::Head => Action::Head,
+            Slot::Body => return None,
+        };
+        Some(action)
+    }
 }
 
 #[derive(Copy, Clone)]
 pub struct Ability {
-    /// System to run when this ability is added to an Entity.
-    pub setup: SystemId<In<Entity>>,
+    /// System to run when this ability is added to an Entity, along with the
+    /// nodes picked from its skill tree.
+    pub setup: SystemId<In<(Entity, BTreeSet<NodeId>)>>,
     /// Main system when this ability is used.
     pub fire: SystemId<In<Entity>>,
 }
@@ -83,7 +175,7 @@ impl Ability {
     pub fn new<Marker1, Marker2>(
         world: &mut World,
         system: impl IntoSystem<In<Entity>, (), Marker1> + 'static,
-        setup_system: impl IntoSystem<In<Entity>, (), Marker2> + 'static,
+        setup_system: impl IntoSystem<In<(Entity, BTreeSet<NodeId>)>, (), Marker2> + 'static,
     ) -> Self {
         let system = world.register_system(system);
         let setup_system = world.register_system(setup_system);
@@ -95,7 +187,7 @@ impl Ability {
     }
 }
 
-#[derive(Clone, Debug, Hash, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Clone, Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(transparent)]
 pub struct AbilityId(String);
 
@@ -197,7 +289,7 @@ impl AbilityMap {
 
 impl FromWorld for AbilityMap {
     fn from_world(world: &mut World) -> Self {
-        let noop = Ability::new(world, noop_ability, noop_ability);
+        let noop = Ability::new(world, noop_ability, noop_setup);
         AbilityMap {
             noop,
             map: Default::default(),
@@ -282,36 +374,3 @@ impl FromWorld for AbilityMap {
 //     }
 //     .spawn(commands);
 // }
-
-// fn shotgun(
-//     commands: &mut Commands,
-//     props: &ShotgunProps,
-//     transform: &Transform,
-//     velocity: &Velocity,
-//     shooter: Entity,
-//     ability_offset: &AbilityOffset,
-// ) {
-//     for i in 0..props.n_pellets {
-//         let idx = i as f32;
-//         let n_pellets = props.n_pellets as f32;
-//         let relative_angle = (n_pellets * 0.5 - idx) / n_pellets *
-//     props.spread;     let relative_angle =
-//     Quat::from_rotation_z(relative_angle);     let dir =
-//     (transform.rotation * relative_angle) * FORWARD;     let position =
-//             transform.translation + dir * (PLAYER_R + props.radius * 2.0) +
-//     ability_offset.to_vec();     let velocity = dir * props.speed +
-//     velocity.linvel;     BulletSpawner {
-//             position,
-//             velocity,
-//             radius: props.radius,
-//             mass: props.mass,
-//             bullet: Bullet {
-//                 shooter,
-//                 expires_in: props.duration,
-//                 damage: props.damage,
-//             },
-//             health: Health::new(props.bullet_health),
-//         }
-//         .spawn(commands);
-//     }
-// }
@@ -0,0 +1,407 @@
+//! Passives, which go in the body slot.
+//!
+//! They have no button, cost, or cooldown; they change the character that has
+//! them when it spawns, and may keep working in the background after.
+
+use std::collections::BTreeSet;
+use std::marker::PhantomData;
+
+use bevy_app::Plugin;
+use bevy_app::Startup;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::EventReader;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Vec3;
+use bevy_rapier3d::prelude::ColliderMassProperties;
+use bevy_transform::components::Transform;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::noop_ability;
+use super::props::Validate;
+use super::props::Validator;
+use super::tree::run_with_props;
+use super::tree::NodeId;
+use super::tree::SkillTrees;
+use super::Ability;
+use super::AbilityId;
+use super::AbilityMap;
+use super::NonArmSlot;
+use crate::movement::MaxSpeed;
+use crate::AbilityOffset;
+use crate::DamageEvent;
+use crate::Energy;
+use crate::FootOffset;
+use crate::GameSet;
+use crate::Health;
+use crate::SCHEDULE;
+
+pub struct PassivePlugin;
+impl Plugin for PassivePlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            PassiveDefPlugin::<FramePassive<LightFrame>>::default(),
+            PassiveDefPlugin::<FramePassive<HeavyFrame>>::default(),
+            PassiveDefPlugin::<CapacitorPassive>::default(),
+            PassiveDefPlugin::<SiphonPassive>::default(),
+            PassiveDefPlugin::<DynamoPassive>::default(),
+        ))
+        .add_systems(
+            SCHEDULE,
+            (siphon_system, dynamo_system).in_set(GameSet::Stuff),
+        );
+    }
+}
+
+pub trait PassiveDef: Send + Sync + Sized + 'static {
+    type Props: Resource + Default + Validate + Serialize + DeserializeOwned;
+
+    fn id() -> AbilityId;
+
+    /// Register what this passive does to a character as it spawns.
+    fn setup(world: &mut World) -> SystemId<In<Entity>>;
+}
+
+/// Adds a `PassiveDef`'s props, and registers it for the body slot.
+pub struct PassiveDefPlugin<P: PassiveDef> {
+    _marker: PhantomData<P>,
+}
+
+impl<P: PassiveDef> Default for PassiveDefPlugin<P> {
+    fn default() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<P: PassiveDef> Plugin for PassiveDefPlugin<P> {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.init_resource::<P::Props>()
+            .add_systems(Startup, register::<P>);
+    }
+}
+
+/// The system that does a passive's setup.
+#[derive(Resource)]
+struct Setup<P: PassiveDef> {
+    system: SystemId<In<Entity>>,
+    _marker: PhantomData<P>,
+}
+
+fn register<P: PassiveDef>(world: &mut World) {
+    let system = P::setup(world);
+    world.insert_resource(Setup::<P> {
+        system,
+        _marker: PhantomData,
+    });
+    let ability = Ability {
+        fire: world.register_system(noop_ability),
+        setup: world.register_system(setup::<P>),
+    };
+    world
+        .resource_mut::<AbilityMap>()
+        .register(NonArmSlot::Body, P::id(), ability);
+}
+
+fn setup<P: PassiveDef>(
+    In((entity, picked)): In<(Entity, BTreeSet<NodeId>)>,
+    mut commands: Commands,
+    props: Res<P::Props>,
+    trees: Res<SkillTrees>,
+    setup: Res<Setup<P>>,
+) {
+    let system = setup.system;
+    if let Some(mut tuned) = trees.tune(&P::id(), &*props, &picked) {
+        commands.queue(move |world: &mut World| {
+            run_with_props(world, &mut tuned, system, entity);
+        });
+    } else {
+        commands.run_system_with_input(system, entity);
+    }
+}
+
+pub trait FrameKind: Send + Sync + Sized + 'static {
+    fn id() -> AbilityId;
+}
+
+pub struct LightFrame;
+
+impl FrameKind for LightFrame {
+    fn id() -> AbilityId {
+        AbilityId::from("light_frame")
+    }
+}
+
+pub struct HeavyFrame;
+
+impl FrameKind for HeavyFrame {
+    fn id() -> AbilityId {
+        AbilityId::from("heavy_frame")
+    }
+}
+
+/// Trades size and health for speed, one way or the other.
+pub struct FramePassive<K: FrameKind> {
+    _marker: PhantomData<K>,
+}
+impl<K: FrameKind> PassiveDef for FramePassive<K>
+where
+    FrameProps<K>: Default,
+{
+    type Props = FrameProps<K>;
+
+    fn id() -> AbilityId {
+        K::id()
+    }
+
+    fn setup(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(frame::<K>)
+    }
+}
+
+/// Each of these multiplies the character's base value.
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct FrameProps<K: FrameKind> {
+    size: f32,
+    health: f32,
+    speed: f32,
+    mass: f32,
+    #[serde(skip)]
+    _marker: PhantomData<K>,
+}
+
+impl Default for FrameProps<LightFrame> {
+    fn default() -> Self {
+        Self {
+            size: 0.8,
+            health: 0.75,
+            speed: 1.2,
+            mass: 0.75,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Default for FrameProps<HeavyFrame> {
+    fn default() -> Self {
+        Self {
+            size: 1.25,
+            health: 1.5,
+            speed: 0.8,
+            mass: 1.5,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: FrameKind> Validate for FrameProps<K> {
+    fn validate(&self, v: &mut Validator) {
+        v.positive("size", self.size);
+        v.positive("health", self.health);
+        v.positive("speed", self.speed);
+        v.positive("mass", self.mass);
+    }
+}
+
+fn frame<K: FrameKind>(
+    In(entity): In<Entity>,
+    mut user_q: Query<(
+        &mut Transform,
+        &mut FootOffset,
+        &mut AbilityOffset,
+        &mut ColliderMassProperties,
+        &mut Health,
+        &mut MaxSpeed,
+    )>,
+    props: Res<FrameProps<K>>,
+) where
+    FrameProps<K>: Default,
+{
+    let Ok((mut transform, mut foot_offset, mut ability_offset, mut mass, mut health, mut speed)) =
+        user_q.get_mut(entity)
+    else {
+        return;
+    };
+
+    // Scaling the transform scales the collider along with the model.
+    transform.scale = Vec3::splat(props.size);
+    foot_offset.y *= props.size;
+    ability_offset.y *= props.size;
+    if let ColliderMassProperties::Mass(m) = &mut *mass {
+        *m *= props.mass;
+    }
+    health.max *= props.health;
+    health.cur = health.max;
+    *speed *= props.speed;
+}
+
+/// Regenerates energy faster.
+pub struct CapacitorPassive;
+impl PassiveDef for CapacitorPassive {
+    type Props = CapacitorProps;
+
+    fn id() -> AbilityId {
+        AbilityId::from("capacitor")
+    }
+
+    fn setup(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(capacitor)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct CapacitorProps {
+    /// Extra energy regen per frame.
+    regen: f32,
+}
+
+impl Default for CapacitorProps {
+    fn default() -> Self {
+        Self { regen: 0.15 }
+    }
+}
+
+impl Validate for CapacitorProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("regen", self.regen);
+    }
+}
+
+fn capacitor(In(entity): In<Entity>, mut user_q: Query<&mut Energy>, props: Res<CapacitorProps>) {
+    if let Ok(mut energy) = user_q.get_mut(entity) {
+        energy.regen += props.regen;
+    }
+}
+
+/// Gains energy for damage dealt.
+///
+/// Note: This only counts damage that sends a `DamageEvent`, so not things
+/// like burning or discharge, which nobody gets credit for.
+pub struct SiphonPassive;
+impl PassiveDef for SiphonPassive {
+    type Props = SiphonProps;
+
+    fn id() -> AbilityId {
+        AbilityId::from("siphon")
+    }
+
+    fn setup(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(siphon)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct SiphonProps {
+    /// Energy per point of damage.
+    ratio: f32,
+}
+
+impl Default for SiphonProps {
+    fn default() -> Self {
+        Self { ratio: 0.5 }
+    }
+}
+
+impl Validate for SiphonProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("ratio", self.ratio);
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Siphon {
+    ratio: f32,
+}
+
+fn siphon(In(entity): In<Entity>, mut commands: Commands, props: Res<SiphonProps>) {
+    commands
+        .entity(entity)
+        .try_insert(Siphon { ratio: props.ratio });
+}
+
+fn siphon_system(
+    mut damage_events: EventReader<DamageEvent>,
+    mut user_q: Query<(&Siphon, &mut Energy)>,
+) {
+    for event in damage_events.read() {
+        if let Ok((siphon, mut energy)) = user_q.get_mut(event.source) {
+            energy.cur = (energy.cur + event.amount * siphon.ratio).min(energy.max);
+        }
+    }
+}
+
+/// Gains energy for damage taken, from anything.
+pub struct DynamoPassive;
+impl PassiveDef for DynamoPassive {
+    type Props = DynamoProps;
+
+    fn id() -> AbilityId {
+        AbilityId::from("dynamo")
+    }
+
+    fn setup(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(dynamo)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct DynamoProps {
+    /// Energy per point of damage.
+    ratio: f32,
+}
+
+impl Default for DynamoProps {
+    fn default() -> Self {
+        Self { ratio: 0.5 }
+    }
+}
+
+impl Validate for DynamoProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("ratio", self.ratio);
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Dynamo {
+    ratio: f32,
+    /// `Health::taken`, as of last frame.
+    last: f32,
+}
+
+fn dynamo(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<&Health>,
+    props: Res<DynamoProps>,
+) {
+    let Ok(health) = user_q.get(entity) else {
+        return;
+    };
+    commands.entity(entity).try_insert(Dynamo {
+        ratio: props.ratio,
+        last: health.taken,
+    });
+}
+
+fn dynamo_system(mut user_q: Query<(&mut Dynamo, &Health, &mut Energy)>) {
+    for (mut dynamo, health, mut energy) in &mut user_q {
+        // We watch what health has taken, rather than damage events, so that
+        // everything counts; but not shields fading, or health just dropping.
+        let lost = health.taken - dynamo.last;
+        if lost > 0.0 {
+            energy.cur = (energy.cur + lost * dynamo.ratio).min(energy.max);
+        }
+        dynamo.last = health.taken;
+    }
+}
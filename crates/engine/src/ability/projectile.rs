@@ -0,0 +1,309 @@
+//! What projectiles do to each other when they touch.
+//!
+//! Bullets, rockets, and grenades are all `Projectile`s. When two meet, the
+//! `InteractionMatrix` says what each does to the other, by their kinds.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::QueryData;
+use bevy_ecs::query::With;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_math::Vec3;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::props::Validate;
+use super::props::Validator;
+use crate::collision::TrackCollisions;
+use crate::status_effect::TimeDilation;
+use crate::GameSet;
+use crate::Health;
+use crate::SCHEDULE;
+
+pub struct ProjectilePlugin;
+impl Plugin for ProjectilePlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.init_resource::<InteractionMatrix>().add_systems(
+            SCHEDULE,
+            collision_system
+                .after(super::bullet::collision_system)
+                .in_set(GameSet::Collision),
+        );
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ProjectileKind {
+    Bullet,
+    Rocket,
+    Grenade,
+}
+
+#[derive(Component, Copy, Clone, Debug)]
+pub struct Projectile {
+    pub kind: ProjectileKind,
+    /// Who gets the credit for what this does, including anything it sets off
+    /// when it dies.
+    pub shooter: Entity,
+    /// What this does to projectiles it `Damage`s.
+    pub damage: f32,
+}
+
+/// What happens to a projectile when another hits it.
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Interaction {
+    /// It dies on the spot, setting off anything it does on death.
+    Destroy,
+    /// It takes the hitter's damage, and dies if that's enough.
+    Damage,
+    /// It bounces off.
+    Deflect,
+    /// Nothing.
+    #[default]
+    PassThrough,
+}
+
+/// What each kind of projectile does to each other kind, as
+/// `hitter: { hit: interaction }`. Missing pairs pass through.
+#[derive(Clone, Debug, Resource, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct InteractionMatrix(pub BTreeMap<ProjectileKind, BTreeMap<ProjectileKind, Interaction>>);
+
+impl InteractionMatrix {
+    /// What happens to `hit` when `hitter` hits it.
+    pub fn get(&self, hitter: ProjectileKind, hit: ProjectileKind) -> Interaction {
+        self.0
+            .get(&hitter)
+            .and_then(|row| row.get(&hit))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+impl Default for InteractionMatrix {
+    fn default() -> Self {
+        use Interaction::*;
+        use ProjectileKind::*;
+
+        let row = |row: [(ProjectileKind, Interaction); 3]| row.into_iter().collect();
+        Self(
+            [
+                // It takes a couple of bullets to set off a grenade or rocket.
+                (
+                    Bullet,
+                    row([(Bullet, Destroy), (Rocket, Damage), (Grenade, Damage)]),
+                ),
+                (
+                    Rocket,
+                    row([(Bullet, Destroy), (Rocket, Destroy), (Grenade, Deflect)]),
+                ),
+                (
+                    Grenade,
+                    row([(Bullet, Destroy), (Rocket, Destroy), (Grenade, PassThrough)]),
+                ),
+            ]
+            .into_iter()
+            .collect(),
+        )
+    }
+}
+
+impl Validate for InteractionMatrix {
+    fn validate(&self, _: &mut Validator) {
+        // Any interaction works for any pair.
+    }
+}
+
+#[derive(QueryData)]
+#[query_data(mutable)]
+struct ProjectileQuery {
+    projectile: &'static mut Projectile,
+    health: &'static mut Health,
+    velocity: &'static mut Velocity,
+    transform: &'static Transform,
+    time_dilation: &'static TimeDilation,
+}
+
+fn collision_system(
+    tracker_q: Query<(Entity, &TrackCollisions), With<Projectile>>,
+    mut projectile_q: Query<ProjectileQuery>,
+    matrix: Res<InteractionMatrix>,
+) {
+    // Both projectiles may be tracking the collision, but it should only
+    // count once.
+    let mut pairs = BTreeSet::new();
+    for (entity, colliding) in &tracker_q {
+        for &target in &colliding.targets {
+            if projectile_q.contains(target) {
+                pairs.insert((entity.min(target), entity.max(target)));
+            }
+        }
+    }
+
+    for (a, b) in pairs {
+        let Ok([mut a, mut b]) = projectile_q.get_many_mut([a, b]) else {
+            continue;
+        };
+        let (a_hitter, a_position) = (*a.projectile, a.transform.translation);
+        let (b_hitter, b_position) = (*b.projectile, b.transform.translation);
+
+        let to_b = matrix.get(a_hitter.kind, b_hitter.kind);
+        let to_a = matrix.get(b_hitter.kind, a_hitter.kind);
+        hit(&a_hitter, a_position, &mut b, to_b);
+        hit(&b_hitter, b_position, &mut a, to_a);
+    }
+}
+
+/// Bounces `velocity`, of something at `at`, off something at `from`.
+///
+/// Note: Things already heading away are left alone, so that they don't get
+/// stuck bouncing back and forth while they overlap.
+fn deflect(velocity: Vec3, from: Vec3, at: Vec3) -> Vec3 {
+    let normal = (at - from).with_y(0.0).normalize_or_zero();
+    let toward = velocity.dot(normal);
+    if toward < 0.0 {
+        velocity - 2.0 * toward * normal
+    } else {
+        velocity
+    }
+}
+
+/// Note: Hitting a projectile doesn't send a `DamageEvent`, as it isn't
+/// damage to anyone; otherwise, e.g. Siphon would pay out for shooting bullets.
+fn hit(
+    hitter: &Projectile,
+    from: Vec3,
+    target: &mut ProjectileQueryItem,
+    interaction: Interaction,
+) {
+    match interaction {
+        Interaction::Destroy => target.health.die(),
+        Interaction::Damage => {
+            target.health.take(hitter.damage, target.time_dilation);
+        }
+        Interaction::Deflect => {
+            target.velocity.linvel =
+                deflect(target.velocity.linvel, from, target.transform.translation);
+            return;
+        }
+        Interaction::PassThrough => return,
+    }
+
+    // Credit whoever fired the hitter, so that e.g. shooting a grenade out of
+    // the air counts as theirs, along with the explosion it sets off.
+    if target.health.cur <= 0.0 {
+        target.projectile.shooter = hitter.shooter;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use bevy_ecs::event::Events;
+    use bevy_ecs::system::RunSystemOnce;
+    use bevy_ecs::world::World;
+    use bevy_math::Vec3;
+    use bevy_rapier3d::prelude::Velocity;
+    use bevy_transform::components::Transform;
+
+    use super::collision_system;
+    use super::deflect;
+    use super::Interaction;
+    use super::InteractionMatrix;
+    use super::Projectile;
+    use super::ProjectileKind;
+    use crate::collision::TrackCollisions;
+    use crate::status_effect::TimeDilation;
+    use crate::DamageEvent;
+    use crate::Health;
+
+    #[test]
+    fn matrix_defaults() {
+        let matrix = InteractionMatrix::default();
+        let get = |hitter, hit| matrix.get(hitter, hit);
+        assert_eq!(
+            get(ProjectileKind::Bullet, ProjectileKind::Rocket),
+            Interaction::Damage
+        );
+        assert_eq!(
+            get(ProjectileKind::Rocket, ProjectileKind::Grenade),
+            Interaction::Deflect
+        );
+        assert_eq!(
+            get(ProjectileKind::Grenade, ProjectileKind::Grenade),
+            Interaction::PassThrough
+        );
+
+        // Anything missing passes through.
+        let empty = InteractionMatrix(Default::default());
+        assert_eq!(
+            empty.get(ProjectileKind::Rocket, ProjectileKind::Rocket),
+            Interaction::PassThrough
+        );
+    }
+
+    #[test]
+    fn counts_each_pair_once() {
+        let mut world = World::new();
+        world.init_resource::<InteractionMatrix>();
+        world.init_resource::<Events<DamageEvent>>();
+
+        let shooter = world.spawn_empty().id();
+        let projectile = |kind, damage| {
+            (
+                Projectile {
+                    kind,
+                    shooter,
+                    damage,
+                },
+                Health::new(3.0),
+                Velocity::zero(),
+                Transform::default(),
+                TimeDilation::default(),
+                TrackCollisions::default(),
+            )
+        };
+        let bullet = world.spawn(projectile(ProjectileKind::Bullet, 1.0)).id();
+        let rocket = world.spawn(projectile(ProjectileKind::Rocket, 0.0)).id();
+        // Both sides track the collision.
+        world
+            .get_mut::<TrackCollisions>(bullet)
+            .unwrap()
+            .targets
+            .push(rocket);
+        world
+            .get_mut::<TrackCollisions>(rocket)
+            .unwrap()
+            .targets
+            .push(bullet);
+
+        world.run_system_once(collision_system).unwrap();
+
+        assert_eq!(world.get::<Health>(rocket).unwrap().cur, 2.0);
+        assert_eq!(world.get::<Health>(bullet).unwrap().cur, 0.0);
+        // Projectiles aren't anyone, so there's no damage to credit.
+        assert_eq!(world.resource::<Events<DamageEvent>>().len(), 0);
+    }
+
+    #[test]
+    fn deflects_toward_only() {
+        let from = Vec3::ZERO;
+        let at = Vec3::new(1.0, 0.0, 0.0);
+
+        // Heading at the deflector, so it bounces straight back.
+        let velocity = Vec3::new(-2.0, 0.0, 1.0);
+        assert_eq!(deflect(velocity, from, at), Vec3::new(2.0, 0.0, 1.0));
+
+        // Already heading away, so nothing changes.
+        let velocity = Vec3::new(2.0, 0.0, 1.0);
+        assert_eq!(deflect(velocity, from, at), velocity);
+    }
+}
@@ -0,0 +1,484 @@
+//! Ability properties, loaded from a data file.
+//!
+//! Every ability has built-in props, but we replace them with those in
+//! `ABILITY_PROPS` when it exists, so they can be tuned without recompiling.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use bevy_app::First;
+use bevy_app::Plugin;
+use bevy_ecs::system::Resource;
+use bevy_ecs::world::World;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::capacitor_field::CapacitorFieldProps;
+use super::cone::ConeProps;
+use super::cone::Cryo;
+use super::cone::Flamethrower;
+use super::dilation_field::DilationFieldProps;
+use super::dilation_field::HasteField;
+use super::dilation_field::SlowField;
+use super::energy_beam::EnergyBeamProps;
+use super::gravity_ball::GravityBallProps;
+use super::grenade::FragGrenade;
+use super::grenade::GrenadeProps;
+use super::grenade::HealGrenade;
+use super::gun::ColdGun;
+use super::gun::FireGun;
+use super::gun::GunProps;
+use super::gun::ShockGun;
+use super::gun::Shotgun;
+use super::gun::StandardGun;
+use super::head::OverclockProps;
+use super::head::ScanProps;
+use super::head::TargetingComputerProps;
+use super::heal_beam::HealBeamProps;
+use super::knockback::BlastProps;
+use super::knockback::HookProps;
+use super::laser::LaserProps;
+use super::melee::MeleeProps;
+use super::mobility::BlinkProps;
+use super::mobility::JumpProps;
+use super::passive::CapacitorProps;
+use super::passive::DynamoProps;
+use super::passive::FrameProps;
+use super::passive::HeavyFrame;
+use super::passive::LightFrame;
+use super::passive::SiphonProps;
+use super::projectile::InteractionMatrix;
+use super::rocket::RocketProps;
+use super::shield::BarrierProps;
+use super::shield::PlantedShieldProps;
+use super::transport::TransportProps;
+use super::tree::SkillTrees;
+use super::turret::TurretProps;
+use crate::time::Dur;
+
+pub const ABILITY_PROPS: &str = "assets/props/abilities.ron";
+
+/// Bump this whenever the props file changes in a way that old files won't
+/// work with.
+pub const PROPS_VERSION: u32 = 3;
+
+/// Something with values that need checking when we load them.
+pub trait Validate {
+    fn validate(&self, v: &mut Validator);
+}
+
+/// Collects problems with props, along with where we found them.
+#[derive(Debug, Default)]
+pub struct Validator {
+    path: Vec<&'static str>,
+    errors: Vec<String>,
+}
+
+impl Validator {
+    pub fn check(&mut self, name: &str, ok: bool, problem: &str) {
+        if !ok {
+            let mut path = self.path.join(".");
+            if !path.is_empty() {
+                path.push('.');
+            }
+            self.errors.push(format!("{path}{name} {problem}"));
+        }
+    }
+
+    pub fn finite(&mut self, name: &str, value: f32) {
+        self.check(name, value.is_finite(), "must be a number");
+    }
+
+    pub fn positive(&mut self, name: &str, value: f32) {
+        self.check(name, value.is_finite() && value > 0.0, "must be positive");
+    }
+
+    pub fn non_negative(&mut self, name: &str, value: f32) {
+        self.check(
+            name,
+            value.is_finite() && value >= 0.0,
+            "must not be negative",
+        );
+    }
+
+    pub fn duration(&mut self, name: &str, value: Dur) {
+        self.non_negative(name, value / Dur::new(1));
+    }
+
+    pub fn positive_duration(&mut self, name: &str, value: Dur) {
+        self.positive(name, value / Dur::new(1));
+    }
+
+    pub fn nested<T: Validate>(&mut self, name: &'static str, value: &T) {
+        self.path.push(name);
+        value.validate(self);
+        self.path.pop();
+    }
+
+    pub fn into_errors(self) -> Vec<String> {
+        self.errors
+    }
+}
+
+#[derive(Debug)]
+pub enum PropsError {
+    Io(io::Error),
+    Parse(ron::error::SpannedError),
+    Version { found: u32 },
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for PropsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PropsError::Io(err) => write!(f, "{err}"),
+            PropsError::Parse(err) => write!(f, "{err}"),
+            PropsError::Version { found } => {
+                write!(
+                    f,
+                    "version {found} is not supported; expected {PROPS_VERSION}"
+                )
+            }
+            PropsError::Invalid(errors) => write!(f, "invalid props: {}", errors.join("; ")),
+        }
+    }
+}
+
+impl Error for PropsError {}
+
+impl From<io::Error> for PropsError {
+    fn from(err: io::Error) -> Self {
+        PropsError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for PropsError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        PropsError::Parse(err)
+    }
+}
+
+/// A hash of the props in use, so different machines can make sure they match.
+#[derive(Resource, Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PropsChecksum(pub [u8; 32]);
+
+impl fmt::Display for PropsChecksum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The props for every ability, as stored in a file.
+#[derive(Serialize, Deserialize)]
+pub struct PropsFile {
+    pub version: u32,
+    pub gun: GunProps<StandardGun>,
+    pub fire_gun: GunProps<FireGun>,
+    pub cold_gun: GunProps<ColdGun>,
+    pub shotgun: GunProps<Shotgun>,
+    pub rocket: RocketProps,
+    pub frag_grenade: GrenadeProps<FragGrenade>,
+    pub heal_grenade: GrenadeProps<HealGrenade>,
+    pub gravity_ball: GravityBallProps,
+    pub transport: TransportProps,
+    pub melee: MeleeProps,
+    pub laser: LaserProps,
+    pub flamethrower: ConeProps<Flamethrower>,
+    pub cryo: ConeProps<Cryo>,
+    pub heal_beam: HealBeamProps,
+    pub turret: TurretProps,
+    pub barrier: BarrierProps,
+    pub planted_shield: PlantedShieldProps,
+    pub blink: BlinkProps,
+    pub jump: JumpProps,
+    pub slow_field: DilationFieldProps<SlowField>,
+    pub haste_field: DilationFieldProps<HasteField>,
+    pub hook: HookProps,
+    pub concussive_blast: BlastProps,
+    pub energy_beam: EnergyBeamProps,
+    pub scan: ScanProps,
+    pub targeting_computer: TargetingComputerProps,
+    pub overclock: OverclockProps,
+    pub light_frame: FrameProps<LightFrame>,
+    pub heavy_frame: FrameProps<HeavyFrame>,
+    pub capacitor: CapacitorProps,
+    pub siphon: SiphonProps,
+    pub dynamo: DynamoProps,
+    pub trees: SkillTrees,
+    pub projectiles: InteractionMatrix,
+    pub shock_gun: GunProps<ShockGun>,
+    pub capacitor_field: CapacitorFieldProps,
+}
+
+impl Default for PropsFile {
+    /// The built-in props.
+    fn default() -> Self {
+        Self {
+            version: PROPS_VERSION,
+            gun: GunProps::default(),
+            fire_gun: GunProps::default(),
+            cold_gun: GunProps::default(),
+            shotgun: GunProps::default(),
+            rocket: RocketProps::default(),
+            frag_grenade: GrenadeProps::default(),
+            heal_grenade: GrenadeProps::default(),
+            gravity_ball: GravityBallProps::default(),
+            transport: TransportProps::default(),
+            melee: MeleeProps::default(),
+            laser: LaserProps::default(),
+            flamethrower: ConeProps::default(),
+            cryo: ConeProps::default(),
+            heal_beam: HealBeamProps::default(),
+            turret: TurretProps::default(),
+            barrier: BarrierProps::default(),
+            planted_shield: PlantedShieldProps::default(),
+            blink: BlinkProps::default(),
+            jump: JumpProps::default(),
+            slow_field: DilationFieldProps::default(),
+            haste_field: DilationFieldProps::default(),
+            hook: HookProps::default(),
+            concussive_blast: BlastProps::default(),
+            energy_beam: EnergyBeamProps::default(),
+            scan: ScanProps::default(),
+            targeting_computer: TargetingComputerProps::default(),
+            overclock: OverclockProps::default(),
+            light_frame: FrameProps::default(),
+            heavy_frame: FrameProps::default(),
+            capacitor: CapacitorProps::default(),
+            siphon: SiphonProps::default(),
+            dynamo: DynamoProps::default(),
+            trees: SkillTrees::default(),
+            projectiles: InteractionMatrix::default(),
+            shock_gun: GunProps::default(),
+            capacitor_field: CapacitorFieldProps::default(),
+        }
+    }
+}
+
+impl Validate for PropsFile {
+    fn validate(&self, v: &mut Validator) {
+        v.nested("gun", &self.gun);
+        v.nested("fire_gun", &self.fire_gun);
+        v.nested("cold_gun", &self.cold_gun);
+        v.nested("shotgun", &self.shotgun);
+        v.nested("rocket", &self.rocket);
+        v.nested("frag_grenade", &self.frag_grenade);
+        v.nested("heal_grenade", &self.heal_grenade);
+        v.nested("gravity_ball", &self.gravity_ball);
+        v.nested("transport", &self.transport);
+        v.nested("melee", &self.melee);
+        v.nested("laser", &self.laser);
+        v.nested("flamethrower", &self.flamethrower);
+        v.nested("cryo", &self.cryo);
+        v.nested("heal_beam", &self.heal_beam);
+        v.nested("turret", &self.turret);
+        v.nested("barrier", &self.barrier);
+        v.nested("planted_shield", &self.planted_shield);
+        v.nested("blink", &self.blink);
+        v.nested("jump", &self.jump);
+        v.nested("slow_field", &self.slow_field);
+        v.nested("haste_field", &self.haste_field);
+        v.nested("hook", &self.hook);
+        v.nested("concussive_blast", &self.concussive_blast);
+        v.nested("energy_beam", &self.energy_beam);
+        v.nested("scan", &self.scan);
+        v.nested("targeting_computer", &self.targeting_computer);
+        v.nested("overclock", &self.overclock);
+        v.nested("light_frame", &self.light_frame);
+        v.nested("heavy_frame", &self.heavy_frame);
+        v.nested("capacitor", &self.capacitor);
+        v.nested("siphon", &self.siphon);
+        v.nested("dynamo", &self.dynamo);
+        v.nested("trees", &self.trees);
+        v.nested("projectiles", &self.projectiles);
+        v.nested("shock_gun", &self.shock_gun);
+        v.nested("capacitor_field", &self.capacitor_field);
+    }
+}
+
+impl PropsFile {
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self, PropsError> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    pub fn parse(s: &str) -> Result<Self, PropsError> {
+        let file: Self = ron::from_str(s)?;
+        if file.version != PROPS_VERSION {
+            return Err(PropsError::Version {
+                found: file.version,
+            });
+        }
+
+        let mut validator = Validator::default();
+        file.validate(&mut validator);
+        if !validator.errors.is_empty() {
+            return Err(PropsError::Invalid(validator.errors));
+        }
+
+        Ok(file)
+    }
+
+    /// A hash of the values in this file.
+    ///
+    /// Note: We hash the values rather than the file, so formatting and
+    /// comments don't matter.
+    pub fn checksum(&self) -> PropsChecksum {
+        let canonical = ron::to_string(self).expect("props should always serialize");
+        PropsChecksum(*blake3::hash(canonical.as_bytes()).as_bytes())
+    }
+
+    fn insert(self, world: &mut World) {
+        world.insert_resource(self.checksum());
+        world.insert_resource(self.gun);
+        world.insert_resource(self.fire_gun);
+        world.insert_resource(self.cold_gun);
+        world.insert_resource(self.shotgun);
+        world.insert_resource(self.rocket);
+        world.insert_resource(self.frag_grenade);
+        world.insert_resource(self.heal_grenade);
+        world.insert_resource(self.gravity_ball);
+        world.insert_resource(self.transport);
+        world.insert_resource(self.melee);
+        world.insert_resource(self.laser);
+        world.insert_resource(self.flamethrower);
+        world.insert_resource(self.cryo);
+        world.insert_resource(self.heal_beam);
+        world.insert_resource(self.turret);
+        world.insert_resource(self.barrier);
+        world.insert_resource(self.planted_shield);
+        world.insert_resource(self.blink);
+        world.insert_resource(self.jump);
+        world.insert_resource(self.slow_field);
+        world.insert_resource(self.haste_field);
+        world.insert_resource(self.hook);
+        world.insert_resource(self.concussive_blast);
+        world.insert_resource(self.energy_beam);
+        world.insert_resource(self.scan);
+        world.insert_resource(self.targeting_computer);
+        world.insert_resource(self.overclock);
+        world.insert_resource(self.light_frame);
+        world.insert_resource(self.heavy_frame);
+        world.insert_resource(self.capacitor);
+        world.insert_resource(self.siphon);
+        world.insert_resource(self.dynamo);
+        world.insert_resource(self.trees);
+        world.insert_resource(self.projectiles);
+        world.insert_resource(self.shock_gun);
+        world.insert_resource(self.capacitor_field);
+    }
+}
+
+/// Loads ability props, replacing the built-in ones.
+///
+/// This needs to be added after every ability plugin.
+pub struct PropsPlugin {
+    pub path: PathBuf,
+}
+
+impl Default for PropsPlugin {
+    fn default() -> Self {
+        Self {
+            path: ABILITY_PROPS.into(),
+        }
+    }
+}
+
+impl Plugin for PropsPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        let file = match PropsFile::load(&self.path) {
+            Ok(file) => file,
+            Err(PropsError::Io(err)) if err.kind() == io::ErrorKind::NotFound => {
+                tracing::warn!(path = ?self.path, "No props file; using built-in props");
+                PropsFile::default()
+            }
+            Err(err) => panic!("Could not load props from {:?}: {err}", self.path),
+        };
+        tracing::info!(checksum = %file.checksum(), "Loaded ability props");
+        file.insert(app.world_mut());
+    }
+}
+
+/// Reloads ability props whenever their file changes.
+///
+/// Note: This is only for development. Reloads happen outside `SCHEDULE`, but
+/// each machine still reloads whenever it sees the change, so don't use it in
+/// multiplayer.
+pub struct PropsHotReloadPlugin {
+    pub path: PathBuf,
+}
+
+impl Default for PropsHotReloadPlugin {
+    fn default() -> Self {
+        Self {
+            path: ABILITY_PROPS.into(),
+        }
+    }
+}
+
+impl Plugin for PropsHotReloadPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.insert_resource(PropsWatcher {
+            path: self.path.clone(),
+            modified: modified(&self.path),
+        })
+        .add_systems(First, hot_reload_system);
+    }
+}
+
+#[derive(Resource)]
+struct PropsWatcher {
+    path: PathBuf,
+    modified: Option<SystemTime>,
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Reload props when their file changes.
+fn hot_reload_system(world: &mut World) {
+    let mut watcher = world.resource_mut::<PropsWatcher>();
+    let modified = modified(&watcher.path);
+    if modified == watcher.modified {
+        return;
+    }
+    watcher.modified = modified;
+    let path = watcher.path.clone();
+
+    match PropsFile::load(&path) {
+        Ok(file) => {
+            tracing::info!(checksum = %file.checksum(), "Reloaded ability props");
+            file.insert(world);
+        }
+        Err(err) => tracing::error!(%err, "Could not reload props; keeping the old ones"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::PropsFile;
+    use super::ABILITY_PROPS;
+
+    #[test]
+    fn props_file_is_valid() {
+        let path = format!("{}/../../{ABILITY_PROPS}", env!("CARGO_MANIFEST_DIR"));
+        if let Err(err) = PropsFile::load(path) {
+            panic!("{err}");
+        }
+    }
+
+    #[test]
+    fn rejects_invalid_props() {
+        let mut file = PropsFile::default();
+        file.rocket.mass = -1.0;
+        let s = ron::to_string(&file).unwrap();
+        assert!(PropsFile::parse(&s).is_err());
+    }
+}
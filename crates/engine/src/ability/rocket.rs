@@ -1,19 +1,21 @@
 use std::f32::consts::PI;
-use std::marker::PhantomData;
 
 use bevy_app::Plugin;
-use bevy_app::Startup;
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
+use bevy_ecs::query::Has;
 use bevy_ecs::query::QueryData;
 use bevy_ecs::query::With;
+use bevy_ecs::query::Without;
 use bevy_ecs::schedule::IntoSystemConfigs;
 use bevy_ecs::system::Commands;
 use bevy_ecs::system::In;
 use bevy_ecs::system::Query;
 use bevy_ecs::system::Res;
 use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
 use bevy_ecs::world::World;
+use bevy_math::Vec2;
 use bevy_math::Vec3;
 use bevy_rapier3d::prelude::Collider;
 use bevy_rapier3d::prelude::ExternalForce;
@@ -22,19 +24,25 @@ use bevy_rapier3d::prelude::RigidBody;
 use bevy_rapier3d::prelude::Sensor;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
 
-use super::cooldown::Cooldown;
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::def::Trigger;
 use super::explosion::ExplosionCallback;
 use super::explosion::ExplosionKind;
 use super::explosion::ExplosionProps;
-use super::noop_ability;
-use super::Ability;
+use super::head::TargetingComputer;
+use super::magazine::MagazineProps;
+use super::projectile::Projectile;
+use super::projectile::ProjectileKind;
+use super::props::Validate;
+use super::props::Validator;
 use super::AbilityId;
-use super::AbilityMap;
-use super::Left;
-use super::Right;
 use super::Side;
-use super::SideEnum;
 use crate::collision::TrackCollisionBundle;
 use crate::collision::TrackCollisions;
 use crate::level::InLevel;
@@ -42,10 +50,12 @@ use crate::lifecycle::DeathCallback;
 use crate::movement::DesiredMove;
 use crate::movement::MaxSpeed;
 use crate::status_effect::StatusProps;
-use crate::status_effect::TimeDilation;
 use crate::time::Dur;
 use crate::time::TIMESTEP;
 use crate::AbilityOffset;
+use crate::Ally;
+use crate::CharacterMarker;
+use crate::Enemy;
 use crate::Energy;
 use crate::GameSet;
 use crate::Health;
@@ -61,19 +71,47 @@ use crate::SCHEDULE;
 pub struct RocketPlugin;
 impl Plugin for RocketPlugin {
     fn build(&self, app: &mut bevy_app::App) {
-        app.insert_resource(RocketProps::default())
-            .add_systems(Startup, register)
+        app.add_plugins(AbilityDefPlugin::<RocketAbility>::default())
             .add_systems(
                 SCHEDULE,
-                (
-                    (cooldown_system::<Left>, cooldown_system::<Right>).in_set(GameSet::Reset),
-                    (tracking_system, collision_system).in_set(GameSet::Stuff),
-                ),
+                (tracking_system, collision_system).in_set(GameSet::Stuff),
             );
     }
 }
 
-#[derive(Resource)]
+pub struct RocketAbility;
+impl AbilityDef for RocketAbility {
+    type Props = RocketProps;
+
+    const SLOTS: SlotKind = SlotKind::Arm;
+
+    const SECONDARY_TRIGGER: Trigger = Trigger::Press;
+
+    fn id() -> AbilityId {
+        AbilityId::from("rocket")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+
+    fn secondary<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(secondary)
+    }
+}
+
+/// What the rocket's secondary does.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum RocketSecondary {
+    /// Blows up all of the user's rockets in flight.
+    #[default]
+    Detonate,
+    /// Toggles whether rockets follow the character nearest the cursor,
+    /// rather than the cursor itself.
+    LockOn,
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
 pub struct RocketProps {
     pub cost: f32,
     pub cooldown: Dur,
@@ -90,6 +128,12 @@ pub struct RocketProps {
     pub energy_cost: f32,
     pub explosion: ExplosionProps,
     pub mass: f32,
+    #[serde(default)]
+    pub secondary: RocketSecondary,
+    /// Rockets don't have a magazine unless given one here. The secondary is
+    /// taken, so it has to reload on its own.
+    #[serde(default)]
+    pub magazine: Option<MagazineProps>,
 }
 
 impl Default for RocketProps {
@@ -117,86 +161,74 @@ impl Default for RocketProps {
                 kind: ExplosionKind::SeekerRocket,
             },
             mass: 2.0,
+            secondary: RocketSecondary::Detonate,
+            magazine: None,
         }
     }
 }
 
-fn register(world: &mut World) {
-    let id = AbilityId::from("rocket");
-
-    let left = (
-        Ability::new(world, fire::<Left>, setup::<Left>),
-        Ability::new(world, noop_ability, noop_ability),
-    );
-    let right = (
-        Ability::new(world, fire::<Right>, setup::<Right>),
-        Ability::new(world, noop_ability, noop_ability),
-    );
-
-    let mut ability_map = world.get_resource_mut::<AbilityMap>().unwrap();
-
-    ability_map.register_arm(SideEnum::Left, id.clone(), left.0, left.1);
-    ability_map.register_arm(SideEnum::Right, id, right.0, right.1);
+impl Validate for RocketProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.non_negative("turning_radius", self.turning_radius);
+        v.positive("capsule_radius", self.capsule_radius);
+        v.non_negative("capsule_length", self.capsule_length);
+        v.positive("health", self.health);
+        v.positive("max_speed.accel", self.max_speed.accel);
+        v.positive("max_speed.speed", self.max_speed.speed);
+        v.non_negative("energy", self.energy);
+        v.non_negative("energy_cost", self.energy_cost);
+        v.nested("explosion", &self.explosion);
+        v.positive("mass", self.mass);
+        if let Some(magazine) = &self.magazine {
+            v.nested("magazine", magazine);
+            v.check(
+                "magazine.auto_reload",
+                magazine.auto_reload,
+                "must be true, as nothing else reloads rockets",
+            );
+        }
+    }
 }
 
-fn cooldown_system<S: Side>(mut query: Query<(&mut Resources<S>, &TimeDilation)>) {
-    for (mut resources, time_dilation) in &mut query {
-        resources.cooldown.tick(time_dilation);
+impl AbilityProps for RocketProps {
+    fn cost(&self) -> f32 {
+        self.cost
     }
-}
 
-fn setup<S: Side>(entity: In<Entity>, mut commands: Commands) {
-    commands.entity(*entity).try_insert(Resources::<S>::new());
-}
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
 
-#[derive(Component)]
-struct Resources<S: Side> {
-    cooldown: Cooldown,
-    _marker: PhantomData<S>,
-}
-impl<S: Side> Resources<S> {
-    fn new() -> Self {
-        Self {
-            cooldown: Cooldown::new(),
-            _marker: PhantomData,
-        }
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+
+    fn magazine(&self) -> Option<&MagazineProps> {
+        self.magazine.as_ref()
     }
 }
 
 #[derive(QueryData)]
-#[query_data(mutable)]
-struct FireQuery<S: Side> {
+struct FireQuery {
     entity: Entity,
-    gcd: &'static mut Cooldown,
-    energy: &'static mut Energy,
     transform: &'static Transform,
     velocity: &'static Velocity,
     ability_offset: &'static AbilityOffset,
-    resources: &'static mut Resources<S>,
-    time_dilation: &'static TimeDilation,
 }
-fn fire<S: Side>(
+fn fire(
     In(entity): In<Entity>,
     mut commands: Commands,
-    mut user_q: Query<FireQuery<S>>,
+    user_q: Query<FireQuery>,
     props: Res<RocketProps>,
     explosion_callback: Res<ExplosionCallback>,
 ) {
-    let Ok(mut user) = user_q.get_mut(entity) else {
+    let Ok(user) = user_q.get(entity) else {
         return;
     };
 
-    if !user.gcd.is_available(user.time_dilation) {
-        return;
-    }
-
-    if user.resources.cooldown.is_available(user.time_dilation) && user.energy.try_use(props.cost) {
-        user.resources.cooldown.set(props.cooldown);
-        user.gcd.set(props.gcd);
-    } else {
-        return;
-    }
-
     let mut transform = *user.transform;
     let dir = transform.rotation * FORWARD;
     // TODO: If the rocket spawns inside a wall, no one will be hurt by its
@@ -207,12 +239,11 @@ fn fire<S: Side>(
 
     commands.spawn((
         Object {
-            transform: transform
-                .with_scale(Vec3::new(
-                    props.capsule_radius,
-                    props.capsule_radius,
-                    props.capsule_length * 0.5,
-                )),
+            transform: transform.with_scale(Vec3::new(
+                props.capsule_radius,
+                props.capsule_radius,
+                props.capsule_length * 0.5,
+            )),
             collider: Collider::capsule_z(1.0, 1.0),
             foot_offset: (-props.capsule_radius).into(),
             mass: MassBundle::new(props.mass),
@@ -236,6 +267,11 @@ fn fire<S: Side>(
             turning_radius: props.turning_radius,
             energy_cost: props.energy_cost,
         },
+        Projectile {
+            kind: ProjectileKind::Rocket,
+            shooter: user.entity,
+            damage: 0.0,
+        },
         props.explosion,
         Shootable,
         props.max_speed,
@@ -248,6 +284,38 @@ fn fire<S: Side>(
     ));
 }
 
+/// The user's rockets follow the character nearest their cursor.
+#[derive(Component, Debug)]
+pub struct LockOn;
+
+fn secondary(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<Has<LockOn>>,
+    mut rocket_q: Query<(&Rocket, &mut Health)>,
+    props: Res<RocketProps>,
+) {
+    let Ok(locked_on) = user_q.get(entity) else {
+        return;
+    };
+
+    match props.secondary {
+        RocketSecondary::Detonate => {
+            for (rocket, mut health) in &mut rocket_q {
+                if rocket.shooter == entity {
+                    health.die();
+                }
+            }
+        }
+        RocketSecondary::LockOn if locked_on => {
+            commands.entity(entity).remove::<LockOn>();
+        }
+        RocketSecondary::LockOn => {
+            commands.entity(entity).insert(LockOn);
+        }
+    }
+}
+
 #[derive(Component)]
 pub struct Rocket {
     pub shooter: Entity,
@@ -256,6 +324,14 @@ pub struct Rocket {
     pub energy_cost: f32,
 }
 
+/// The closest of `candidates` to `from`.
+fn nearest<'a>(from: Vec2, candidates: impl Iterator<Item = &'a Transform>) -> Option<Vec2> {
+    candidates.map(|t| t.translation.to_2d()).min_by(|a, b| {
+        from.distance_squared(*a)
+            .total_cmp(&from.distance_squared(*b))
+    })
+}
+
 fn tracking_system(
     mut query: Query<(
         &Rocket,
@@ -264,18 +340,44 @@ fn tracking_system(
         &mut Energy,
         &mut LockedAxes,
     )>,
-    target_query: Query<&Target>,
+    shooter_q: Query<(
+        &Target,
+        Has<LockOn>,
+        Has<TargetingComputer>,
+        Has<Ally>,
+        Has<Enemy>,
+    )>,
+    character_q: Query<(Entity, &Transform), (With<CharacterMarker>, Without<Rocket>)>,
+    ally_q: Query<&Transform, (With<Ally>, With<CharacterMarker>, Without<Rocket>)>,
+    enemy_q: Query<&Transform, (With<Enemy>, With<CharacterMarker>, Without<Rocket>)>,
 ) {
     for (rocket, mut transform, mut desired_move, mut energy, mut locked_axes) in query.iter_mut() {
         if energy.try_use(rocket.energy_cost) {
-            let Ok(target) = target_query.get(rocket.shooter) else {
+            let Ok((target, locked_on, computer, ally, enemy)) = shooter_q.get(rocket.shooter)
+            else {
                 continue;
             };
-            let target = target.0;
+            let position = transform.translation.to_2d();
+            // Locked on, we go for whoever is nearest the cursor. With a
+            // targeting computer, we go for the nearest foe. Either way, only
+            // if there's anyone to go for.
+            let tracked = match (locked_on, computer, ally, enemy) {
+                (true, ..) => nearest(
+                    target.0,
+                    character_q
+                        .iter()
+                        .filter(|&(character, _)| character != rocket.shooter)
+                        .map(|(_, transform)| transform),
+                ),
+                (_, true, true, _) => nearest(position, enemy_q.iter()),
+                (_, true, _, true) => nearest(position, ally_q.iter()),
+                _ => None,
+            };
+            let target = tracked.unwrap_or(target.0);
 
             let facing = transform.forward().to_2d();
 
-            let desired_rotation = facing.angle_to(target - transform.translation.to_2d());
+            let desired_rotation = facing.angle_to(target - position);
             let rotation = desired_rotation.clamp(-rocket.turning_radius, rocket.turning_radius);
 
             transform.rotate_y(rotation);
@@ -291,7 +393,8 @@ fn tracking_system(
 
 fn collision_system(
     mut rocket_q: Query<(&mut Health, &TrackCollisions, &Velocity, &mut Transform), With<Rocket>>,
-    shootable_q: Query<(), With<Shootable>>,
+    // Other projectiles are handled by the interaction matrix.
+    shootable_q: Query<(), (With<Shootable>, Without<Projectile>)>,
 ) {
     for (mut health, colliding, velocity, mut transform) in &mut rocket_q {
         let should_live = colliding.targets.is_empty()
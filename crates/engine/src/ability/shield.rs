@@ -0,0 +1,265 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_math::Vec3;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::ExternalForce;
+use bevy_rapier3d::prelude::LockedAxes;
+use bevy_rapier3d::prelude::RigidBody;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+use super::Side;
+use crate::collision::TrackCollisionBundle;
+use crate::level::InLevel;
+use crate::lifecycle::Lifetime;
+use crate::status_effect::StatusProps;
+use crate::status_effect::TimeDilation;
+use crate::time::Dur;
+use crate::GameSet;
+use crate::Health;
+use crate::MassBundle;
+use crate::Object;
+use crate::Shootable;
+use crate::FORWARD;
+use crate::PLAYER_HEIGHT;
+use crate::PLAYER_R;
+use crate::SCHEDULE;
+
+pub struct ShieldPlugin;
+impl Plugin for ShieldPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins((
+            AbilityDefPlugin::<BarrierAbility>::default(),
+            AbilityDefPlugin::<PlantedShieldAbility>::default(),
+        ))
+        .add_systems(SCHEDULE, barrier_system.in_set(GameSet::Stuff));
+    }
+}
+
+/// Gives the user a shield, which absorbs damage before their health and decays
+/// over time.
+pub struct BarrierAbility;
+impl AbilityDef for BarrierAbility {
+    type Props = BarrierProps;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        AbilityId::from("barrier")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(barrier)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct BarrierProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    amount: f32,
+    /// Shield lost per frame.
+    decay: f32,
+}
+
+impl Default for BarrierProps {
+    fn default() -> Self {
+        Self {
+            cost: 30.0,
+            cooldown: Dur::new(300),
+            gcd: Dur::new(30),
+            amount: 30.0,
+            decay: 0.05,
+        }
+    }
+}
+
+impl Validate for BarrierProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("amount", self.amount);
+        v.non_negative("decay", self.decay);
+    }
+}
+
+impl AbilityProps for BarrierProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A shield in progress, on the entity it protects.
+///
+/// Note: The shield itself is `Health::shield`; this just decays it.
+#[derive(Component, Debug)]
+pub struct Barrier {
+    decay: f32,
+}
+
+fn barrier(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    mut user_q: Query<&mut Health>,
+    props: Res<BarrierProps>,
+) {
+    let Ok(mut health) = user_q.get_mut(entity) else {
+        return;
+    };
+
+    health.shield = health.shield.max(props.amount);
+    commands
+        .entity(entity)
+        .insert(Barrier { decay: props.decay });
+}
+
+fn barrier_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Health, &Barrier, &TimeDilation)>,
+) {
+    for (entity, mut health, barrier, dilation) in &mut query {
+        health.shield = (health.shield - barrier.decay * dilation.factor()).max(0.0);
+        if health.shield <= 0.0 {
+            commands.entity(entity).remove::<Barrier>();
+        }
+    }
+}
+
+/// Plants a wall in front of the user, which blocks shots until it breaks.
+pub struct PlantedShieldAbility;
+impl AbilityDef for PlantedShieldAbility {
+    type Props = PlantedShieldProps;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        AbilityId::from("planted_shield")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(plant)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct PlantedShieldProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    health: f32,
+    lifetime: Dur,
+    width: f32,
+    thickness: f32,
+}
+
+impl Default for PlantedShieldProps {
+    fn default() -> Self {
+        Self {
+            cost: 40.0,
+            cooldown: Dur::new(600),
+            gcd: Dur::new(30),
+            health: 40.0,
+            lifetime: Dur::new(900),
+            width: 1.2,
+            thickness: 0.1,
+        }
+    }
+}
+
+impl Validate for PlantedShieldProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("health", self.health);
+        v.positive_duration("lifetime", self.lifetime);
+        v.positive("width", self.width);
+        v.positive("thickness", self.thickness);
+    }
+}
+
+impl AbilityProps for PlantedShieldProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+/// A planted shield. Its size is its transform's scale.
+#[derive(Component, Debug)]
+pub struct PlantedShield;
+
+fn plant(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<&Transform>,
+    props: Res<PlantedShieldProps>,
+) {
+    let Ok(transform) = user_q.get(entity) else {
+        return;
+    };
+
+    let dir = transform.rotation * FORWARD;
+    let position = transform.translation + dir * (PLAYER_R * 2.0 + props.thickness);
+
+    commands.spawn((
+        Object {
+            transform: Transform::from_translation(position)
+                .with_rotation(transform.rotation)
+                .with_scale(Vec3::new(props.width, PLAYER_HEIGHT, props.thickness)),
+            collider: Collider::cuboid(0.5, 0.5, 0.5),
+            foot_offset: (-PLAYER_HEIGHT * 0.5).into(),
+            mass: MassBundle::new(1.0),
+            body: RigidBody::Fixed,
+            force: ExternalForce::default(),
+            velocity: Velocity::zero(),
+            locked_axes: LockedAxes::all(),
+            in_level: InLevel,
+            statuses: StatusProps {
+                thermal_mass: 1.0,
+                capacitance: 1.0,
+            }
+            .into(),
+            collisions: TrackCollisionBundle::off(),
+        },
+        Lifetime::new(props.lifetime),
+        Health::new(props.health),
+        Shootable,
+        PlantedShield,
+    ));
+}
@@ -1,5 +1,4 @@
 use bevy_app::Plugin;
-use bevy_app::Startup;
 use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::query::QueryData;
@@ -10,6 +9,7 @@ use bevy_ecs::system::In;
 use bevy_ecs::system::Query;
 use bevy_ecs::system::Res;
 use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
 use bevy_ecs::world::World;
 use bevy_math::Vec2;
 use bevy_rapier3d::prelude::Collider;
@@ -19,11 +19,17 @@ use bevy_rapier3d::prelude::RigidBody;
 use bevy_rapier3d::prelude::Sensor;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::Transform;
-
-use super::cooldown::Cooldown;
-use super::Ability;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
 use super::AbilityId;
-use super::AbilityMap;
+use super::Side;
 use crate::collision::TrackCollisionBundle;
 use crate::collision::TrackCollisions;
 use crate::level::Floor;
@@ -33,7 +39,6 @@ use crate::movement::MaxSpeed;
 use crate::status_effect::StatusProps;
 use crate::status_effect::TimeDilation;
 use crate::time::Dur;
-use crate::Energy;
 use crate::GameSet;
 use crate::Health;
 use crate::MassBundle;
@@ -46,20 +51,31 @@ use crate::SCHEDULE;
 pub struct TransportBeamPlugin;
 impl Plugin for TransportBeamPlugin {
     fn build(&self, app: &mut bevy_app::App) {
-        app.insert_resource(TransportProps::default())
-            .add_systems(Startup, register)
+        app.add_plugins(AbilityDefPlugin::<TransportAbility>::default())
             .add_systems(
                 SCHEDULE,
-                (
-                    cooldown_system.in_set(GameSet::Reset),
-                    (move_system, activation_system).in_set(GameSet::Stuff),
-                ),
+                (move_system, activation_system).in_set(GameSet::Stuff),
             );
     }
 }
 
-#[derive(Resource)]
-struct TransportProps {
+pub struct TransportAbility;
+impl AbilityDef for TransportAbility {
+    type Props = TransportProps;
+
+    const SLOTS: SlotKind = SlotKind::Legs;
+
+    fn id() -> AbilityId {
+        AbilityId::from("transport_beam")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct TransportProps {
     cost: f32,
     cooldown: Dur,
     gcd: Dur,
@@ -85,68 +101,44 @@ impl Default for TransportProps {
     }
 }
 
-fn register(world: &mut World) {
-    let id = AbilityId::from("transport_beam");
-    let ability = Ability::new(world, fire, setup);
-
-    let mut ability_map = world.get_resource_mut::<AbilityMap>().unwrap();
-    ability_map.register(super::NonArmSlot::Legs, id, ability);
+impl Validate for TransportProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("radius", self.radius);
+        v.positive("height", self.height);
+        v.positive("accel", self.accel);
+        v.positive("speed", self.speed);
+        v.duration("delay", self.delay);
+    }
 }
 
-fn cooldown_system(mut query: Query<(&mut Resources, &TimeDilation)>) {
-    for (mut resources, time_dilation) in &mut query {
-        resources.cooldown.tick(time_dilation);
+impl AbilityProps for TransportProps {
+    fn cost(&self) -> f32 {
+        self.cost
     }
-}
 
-fn setup(entity: In<Entity>, mut commands: Commands) {
-    commands.entity(*entity).try_insert(Resources::new());
-}
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
 
-#[derive(Component)]
-struct Resources {
-    cooldown: Cooldown,
-}
-impl Resources {
-    fn new() -> Self {
-        Self {
-            cooldown: Cooldown::new(),
-        }
+    fn gcd(&self) -> Dur {
+        self.gcd
     }
 }
 
-#[derive(QueryData)]
-#[query_data(mutable)]
-struct FireQuery {
-    gcd: &'static mut Cooldown,
-    energy: &'static mut Energy,
-    transform: &'static Transform,
-    resources: &'static mut Resources,
-    time_dilation: &'static TimeDilation,
-    target: &'static Target,
-}
 fn fire(
     In(entity): In<Entity>,
     mut commands: Commands,
-    mut user_q: Query<FireQuery>,
+    user_q: Query<(&Transform, &Target)>,
     props: Res<TransportProps>,
 ) {
-    let Ok(mut user) = user_q.get_mut(entity) else {
+    let Ok((transform, target)) = user_q.get(entity) else {
         return;
     };
 
-    if !user.gcd.is_available(user.time_dilation) {
-        return;
-    }
-
-    if user.resources.cooldown.is_available(user.time_dilation) && user.energy.try_use(props.cost) {
-        user.resources.cooldown.set(props.cooldown);
-        user.gcd.set(props.gcd);
-    } else {
-        return;
-    }
-
-    let mut transform = Transform::from_translation(user.transform.translation);
+    let mut transform = Transform::from_translation(transform.translation);
     transform.translation.y = 0.0;
     commands.spawn((
         Object {
@@ -173,7 +165,7 @@ fn fire(
             activates_in: props.delay,
             radius: props.radius,
             height: props.height,
-            destination: user.target.0,
+            destination: target.0,
         },
         MaxSpeed {
             accel: props.accel,
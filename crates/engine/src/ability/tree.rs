@@ -0,0 +1,361 @@
+//! Skill trees, which specialize abilities.
+//!
+//! An ability may have a tree of nodes, and a loadout picks some of them. Each
+//! node scales fields of the ability's props, usually trading one thing for
+//! another rather than being a straight upgrade.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::fmt;
+
+use bevy_ecs::entity::Entity;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use ron::value::Float;
+use ron::value::Number;
+use ron::Value;
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::props::Validate;
+use super::props::Validator;
+use super::AbilityId;
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct NodeId(String);
+
+impl From<&str> for NodeId {
+    fn from(value: &str) -> Self {
+        NodeId(value.into())
+    }
+}
+
+/// An ability in a loadout, along with the nodes picked from its tree.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(from = "ChoiceRepr")]
+pub struct AbilityChoice {
+    pub id: AbilityId,
+    pub nodes: BTreeSet<NodeId>,
+}
+
+impl From<&str> for AbilityChoice {
+    fn from(value: &str) -> Self {
+        Self {
+            id: value.into(),
+            nodes: BTreeSet::new(),
+        }
+    }
+}
+
+/// Loadouts used to be bare ids, so we still accept those.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ChoiceRepr {
+    Id(AbilityId),
+    Full {
+        id: AbilityId,
+        #[serde(default)]
+        nodes: BTreeSet<NodeId>,
+    },
+}
+
+impl From<ChoiceRepr> for AbilityChoice {
+    fn from(repr: ChoiceRepr) -> Self {
+        match repr {
+            ChoiceRepr::Id(id) => Self {
+                id,
+                nodes: BTreeSet::new(),
+            },
+            ChoiceRepr::Full { id, nodes } => Self { id, nodes },
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillTree {
+    /// How many nodes a loadout can pick.
+    pub points: u32,
+    /// Parents come before their children.
+    pub nodes: Vec<SkillNode>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SkillNode {
+    pub id: NodeId,
+    /// The node that needs picking before this one; roots have none.
+    #[serde(default)]
+    pub parent: Option<NodeId>,
+    /// What to multiply props fields by, by their path, like `bullet.radius`.
+    pub scale: BTreeMap<String, f32>,
+}
+
+#[derive(Debug)]
+pub enum TreeError {
+    NoTree,
+    UnknownNode(NodeId),
+    MissingParent {
+        node: NodeId,
+        parent: NodeId,
+    },
+    TooMany {
+        picked: usize,
+        points: u32,
+    },
+    /// A node scales a field that isn't a number in these props.
+    BadField(String),
+    Props(ron::Error),
+    /// The changed props aren't valid.
+    Invalid(Vec<String>),
+}
+
+impl fmt::Display for TreeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TreeError::NoTree => write!(f, "ability has no skill tree"),
+            TreeError::UnknownNode(node) => write!(f, "unknown node {:?}", node.0),
+            TreeError::MissingParent { node, parent } => {
+                write!(f, "node {:?} needs {:?}", node.0, parent.0)
+            }
+            TreeError::TooMany { picked, points } => {
+                write!(f, "picked {picked} nodes, but only have {points} points")
+            }
+            TreeError::BadField(field) => write!(f, "no number at {field:?}"),
+            TreeError::Props(err) => write!(f, "{err}"),
+            TreeError::Invalid(errors) => write!(f, "invalid props: {}", errors.join("; ")),
+        }
+    }
+}
+
+impl From<ron::Error> for TreeError {
+    fn from(err: ron::Error) -> Self {
+        TreeError::Props(err)
+    }
+}
+
+impl SkillTree {
+    fn node(&self, id: &NodeId) -> Option<&SkillNode> {
+        self.nodes.iter().find(|node| &node.id == id)
+    }
+
+    /// Checks that `picked` is something a loadout can have from this tree.
+    pub fn check(&self, picked: &BTreeSet<NodeId>) -> Result<(), TreeError> {
+        if picked.len() > self.points as usize {
+            return Err(TreeError::TooMany {
+                picked: picked.len(),
+                points: self.points,
+            });
+        }
+        for id in picked {
+            let node = self
+                .node(id)
+                .ok_or_else(|| TreeError::UnknownNode(id.clone()))?;
+            if let Some(parent) = &node.parent {
+                if !picked.contains(parent) {
+                    return Err(TreeError::MissingParent {
+                        node: id.clone(),
+                        parent: parent.clone(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns `props`, as changed by the `picked` nodes.
+    pub fn apply<P>(&self, props: &P, picked: &BTreeSet<NodeId>) -> Result<P, TreeError>
+    where
+        P: Serialize + DeserializeOwned + Validate,
+    {
+        self.check(picked)?;
+
+        // Going through ron lets us get at any props' fields by name.
+        let mut value: Value = ron::from_str(&ron::to_string(props)?).map_err(|err| err.code)?;
+        for node in self.nodes.iter().filter(|node| picked.contains(&node.id)) {
+            for (path, &by) in &node.scale {
+                let fields = path.split('.').collect::<Vec<_>>();
+                scale(&mut value, &fields, by).map_err(|()| TreeError::BadField(path.clone()))?;
+            }
+        }
+        let tuned = P::deserialize(value)?;
+
+        let mut validator = Validator::default();
+        tuned.validate(&mut validator);
+        let errors = validator.into_errors();
+        if !errors.is_empty() {
+            return Err(TreeError::Invalid(errors));
+        }
+        Ok(tuned)
+    }
+}
+
+/// Multiplies the number at `fields` in `value` by `by`.
+fn scale(value: &mut Value, fields: &[&str], by: f32) -> Result<(), ()> {
+    let Some((field, rest)) = fields.split_first() else {
+        let by = by as f64;
+        match value {
+            Value::Number(Number::Float(f)) => *f = Float::new(f.get() * by),
+            Value::Number(Number::Integer(i)) => *i = (*i as f64 * by).round() as i64,
+            _ => return Err(()),
+        }
+        return Ok(());
+    };
+
+    let Value::Map(map) = value else {
+        return Err(());
+    };
+    let key = Value::String(field.to_string());
+    let mut inner = map.remove(&key).ok_or(())?;
+    let result = scale(&mut inner, rest, by);
+    map.insert(key, inner);
+    result
+}
+
+/// Every ability's skill tree, by ability.
+#[derive(Clone, Debug, Resource, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct SkillTrees(pub BTreeMap<AbilityId, SkillTree>);
+
+impl Default for SkillTrees {
+    fn default() -> Self {
+        let node = |id: &str, parent: Option<&str>, scale: &[(&str, f32)]| SkillNode {
+            id: id.into(),
+            parent: parent.map(NodeId::from),
+            scale: scale.iter().map(|&(k, v)| (k.to_string(), v)).collect(),
+        };
+        let gun = SkillTree {
+            points: 2,
+            nodes: vec![
+                node(
+                    "heavy_rounds",
+                    None,
+                    &[("bullet.radius", 1.5), ("speed", 0.7)],
+                ),
+                node(
+                    "hollow_points",
+                    Some("heavy_rounds"),
+                    &[("bullet.damage", 1.5), ("bullet.mass", 0.5)],
+                ),
+                node(
+                    "rapid_fire",
+                    None,
+                    &[("cooldown", 0.6), ("bullet.damage", 0.7)],
+                ),
+                node(
+                    "extended_mag",
+                    None,
+                    &[("magazine.size", 1.5), ("magazine.reload_time", 1.5)],
+                ),
+            ],
+        };
+        Self([(AbilityId::from("gun"), gun)].into_iter().collect())
+    }
+}
+
+impl SkillTrees {
+    /// The props for someone who picked `picked` from `id`'s tree, or `None`
+    /// if they should use the shared ones.
+    pub fn tune<P>(&self, id: &AbilityId, props: &P, picked: &BTreeSet<NodeId>) -> Option<P>
+    where
+        P: Serialize + DeserializeOwned + Validate,
+    {
+        if picked.is_empty() {
+            return None;
+        }
+        let tuned = self
+            .0
+            .get(id)
+            .ok_or(TreeError::NoTree)
+            .and_then(|tree| tree.apply(props, picked));
+        match tuned {
+            Ok(tuned) => Some(tuned),
+            Err(err) => {
+                tracing::error!(?id, ?picked, %err, "Ignoring skill tree nodes");
+                None
+            }
+        }
+    }
+}
+
+impl Validate for SkillTrees {
+    fn validate(&self, v: &mut Validator) {
+        for (id, tree) in &self.0 {
+            let mut seen = BTreeSet::new();
+            for node in &tree.nodes {
+                let name = format!("{}.{}", id.0, node.id.0);
+                if let Some(parent) = &node.parent {
+                    v.check(
+                        &format!("{name}.parent"),
+                        seen.contains(parent),
+                        "must be an earlier node",
+                    );
+                }
+                v.check(&name, seen.insert(node.id.clone()), "is a duplicate");
+                for (path, &by) in &node.scale {
+                    v.positive(&format!("{name}.scale.{path}"), by);
+                }
+            }
+        }
+    }
+}
+
+/// Runs `system` with `props` standing in for the shared ones.
+pub(super) fn run_with_props<P: Resource, O: 'static>(
+    world: &mut World,
+    props: &mut P,
+    system: SystemId<In<Entity>, O>,
+    entity: Entity,
+) -> Option<O> {
+    std::mem::swap(&mut *world.resource_mut::<P>(), props);
+    let output = world
+        .run_system_with_input(system, entity)
+        .inspect_err(|err| tracing::warn!(?entity, %err, "Could not run ability"))
+        .ok();
+    std::mem::swap(&mut *world.resource_mut::<P>(), props);
+    output
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::BTreeSet;
+
+    use super::NodeId;
+    use super::SkillTrees;
+    use crate::ability::gun::GunProps;
+    use crate::ability::gun::StandardGun;
+    use crate::ability::AbilityId;
+
+    fn picks(nodes: &[&str]) -> BTreeSet<NodeId> {
+        nodes.iter().map(|&node| node.into()).collect()
+    }
+
+    #[test]
+    fn nodes_scale_props() {
+        let trees = SkillTrees::default();
+        let tree = &trees.0[&AbilityId::from("gun")];
+        let props = GunProps::<StandardGun>::default();
+
+        let tuned = tree.apply(&props, &picks(&["heavy_rounds"])).unwrap();
+        // We go through text, so allow for rounding.
+        assert!((tuned.bullet.radius - props.bullet.radius * 1.5).abs() < 1e-6);
+        assert!((tuned.speed - props.speed * 0.7).abs() < 1e-5);
+    }
+
+    #[test]
+    fn rejects_bad_picks() {
+        let trees = SkillTrees::default();
+        let tree = &trees.0[&AbilityId::from("gun")];
+
+        assert!(tree.check(&picks(&["hollow_points"])).is_err());
+        assert!(tree.check(&picks(&["nonsense"])).is_err());
+        assert!(tree
+            .check(&picks(&["heavy_rounds", "rapid_fire", "extended_mag"]))
+            .is_err());
+        assert!(tree
+            .check(&picks(&["heavy_rounds", "hollow_points"]))
+            .is_ok());
+    }
+}
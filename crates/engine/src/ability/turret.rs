@@ -0,0 +1,231 @@
+use bevy_app::Plugin;
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::Has;
+use bevy_ecs::query::With;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::In;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::Resource;
+use bevy_ecs::system::SystemId;
+use bevy_ecs::world::World;
+use bevy_rapier3d::prelude::CoefficientCombineRule;
+use bevy_rapier3d::prelude::ExternalForce;
+use bevy_rapier3d::prelude::Friction;
+use bevy_rapier3d::prelude::LockedAxes;
+use bevy_rapier3d::prelude::RigidBody;
+use bevy_rapier3d::prelude::Velocity;
+use bevy_transform::components::Transform;
+use serde::Deserialize;
+use serde::Serialize;
+
+use super::cooldown::Cooldown;
+use super::def::AbilityDef;
+use super::def::AbilityDefPlugin;
+use super::def::AbilityProps;
+use super::def::SlotKind;
+use super::props::Validate;
+use super::props::Validator;
+use super::tree::AbilityChoice;
+use super::AbilityId;
+use super::AbilityMap;
+use super::Side;
+use crate::ai::turret::TurretAi;
+use crate::ai::AiBundle;
+use crate::collision::TrackCollisionBundle;
+use crate::level::InLevel;
+use crate::lifecycle::Lifetime;
+use crate::multiplayer::Buttons;
+use crate::player::character_collider;
+use crate::player::AbilityIds;
+use crate::status_effect::StatusProps;
+use crate::time::Dur;
+use crate::Ally;
+use crate::Character;
+use crate::CharacterMarker;
+use crate::Enemy;
+use crate::Energy;
+use crate::Health;
+use crate::MassBundle;
+use crate::Object;
+use crate::Shootable;
+use crate::ABILITY_Y;
+use crate::CONTACT_SKIN;
+use crate::FORWARD;
+use crate::PLAYER_HEIGHT;
+use crate::PLAYER_MASS;
+use crate::PLAYER_R;
+
+pub struct TurretPlugin;
+impl Plugin for TurretPlugin {
+    fn build(&self, app: &mut bevy_app::App) {
+        app.add_plugins(AbilityDefPlugin::<TurretAbility>::default());
+    }
+}
+
+/// Deploys a turret in front of the user, on their team, with its own
+/// abilities.
+pub struct TurretAbility;
+impl AbilityDef for TurretAbility {
+    type Props = TurretProps;
+
+    const SLOTS: SlotKind = SlotKind::Shoulder;
+
+    fn id() -> AbilityId {
+        AbilityId::from("turret")
+    }
+
+    fn fire<S: Side>(world: &mut World) -> SystemId<In<Entity>> {
+        world.register_system(fire)
+    }
+}
+
+#[derive(Debug, Resource, Serialize, Deserialize)]
+pub struct TurretProps {
+    cost: f32,
+    cooldown: Dur,
+    gcd: Dur,
+    health: f32,
+    energy: f32,
+    energy_regen: f32,
+    lifetime: Dur,
+    /// How many turrets one user can have out at once; deploying another
+    /// replaces the oldest.
+    max_turrets: u32,
+    /// What the turret itself can do.
+    ability_ids: AbilityIds,
+}
+
+impl Default for TurretProps {
+    fn default() -> Self {
+        Self {
+            cost: 40.0,
+            cooldown: Dur::new(300),
+            gcd: Dur::new(30),
+            health: 20.0,
+            energy: 50.0,
+            energy_regen: 0.2,
+            lifetime: Dur::new(1800),
+            max_turrets: 2,
+            ability_ids: AbilityIds {
+                left_arm: AbilityChoice::from("gun"),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl Validate for TurretProps {
+    fn validate(&self, v: &mut Validator) {
+        v.non_negative("cost", self.cost);
+        v.duration("cooldown", self.cooldown);
+        v.duration("gcd", self.gcd);
+        v.positive("health", self.health);
+        v.non_negative("energy", self.energy);
+        v.non_negative("energy_regen", self.energy_regen);
+        v.positive_duration("lifetime", self.lifetime);
+        v.check("max_turrets", self.max_turrets > 0, "must be positive");
+    }
+}
+
+impl AbilityProps for TurretProps {
+    fn cost(&self) -> f32 {
+        self.cost
+    }
+
+    fn cooldown(&self) -> Dur {
+        self.cooldown
+    }
+
+    fn gcd(&self) -> Dur {
+        self.gcd
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Turret {
+    pub owner: Entity,
+}
+
+/// The turrets an entity has deployed, oldest first.
+#[derive(Component, Debug, Default)]
+pub struct Turrets(Vec<Entity>);
+
+fn fire(
+    In(entity): In<Entity>,
+    mut commands: Commands,
+    user_q: Query<(&Transform, Has<Ally>, Has<Enemy>, Option<&Turrets>)>,
+    mut turret_q: Query<&mut Health, With<Turret>>,
+    ability_map: Res<AbilityMap>,
+    props: Res<TurretProps>,
+) {
+    let Ok((transform, ally, enemy, turrets)) = user_q.get(entity) else {
+        return;
+    };
+
+    let mut deployed = turrets.map(|t| t.0.clone()).unwrap_or_default();
+    deployed.retain(|&turret| turret_q.contains(turret));
+    while deployed.len() >= props.max_turrets as usize {
+        let oldest = deployed.remove(0);
+        if let Ok(mut health) = turret_q.get_mut(oldest) {
+            health.die();
+        }
+    }
+
+    let dir = transform.rotation * FORWARD;
+    let position = transform.translation + dir * PLAYER_R * 3.0;
+
+    let mut turret = commands.spawn((
+        Turret { owner: entity },
+        AiBundle::<TurretAi>::default(),
+        Lifetime::new(props.lifetime),
+        Character {
+            health: Health::new(props.health),
+            energy: Energy::new(props.energy, props.energy_regen),
+            object: Object {
+                transform: Transform::from_translation(position).with_rotation(transform.rotation),
+                collider: character_collider(PLAYER_R, PLAYER_HEIGHT),
+                foot_offset: (-PLAYER_HEIGHT * 0.5).into(),
+                body: RigidBody::Fixed,
+                locked_axes: LockedAxes::all(),
+                mass: MassBundle::new(PLAYER_MASS),
+                velocity: Velocity::zero(),
+                force: ExternalForce::default(),
+                in_level: InLevel,
+                statuses: StatusProps {
+                    thermal_mass: 1.0,
+                    capacitance: 1.0,
+                }
+                .into(),
+                collisions: TrackCollisionBundle::off(),
+            },
+            max_speed: Default::default(),
+            friction: Friction {
+                coefficient: 0.0,
+                combine_rule: CoefficientCombineRule::Min,
+            },
+            shootable: Shootable,
+            global_cooldown: Cooldown::new(),
+            desired_movement: Default::default(),
+            ability_offset: ((-PLAYER_HEIGHT * 0.5) + ABILITY_Y.y).into(),
+            marker: CharacterMarker,
+            buttons: Buttons::default(),
+            contact_skin: CONTACT_SKIN,
+        },
+    ));
+    // Turrets fight for whoever deployed them.
+    if ally {
+        turret.insert(Ally);
+    }
+    if enemy {
+        turret.insert(Enemy);
+    }
+    let id = turret.id();
+
+    let abilities = props.ability_ids.build(&ability_map, &mut commands, id);
+    commands.entity(id).insert(abilities);
+
+    deployed.push(id);
+    commands.entity(entity).insert(Turrets(deployed));
+}
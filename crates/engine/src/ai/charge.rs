@@ -10,10 +10,11 @@ use bevy_ecs::system::Commands;
 use bevy_ecs::system::Query;
 use bevy_math::Vec2;
 use bevy_rapier3d::plugin::ReadDefaultRapierContext;
-use bevy_rapier3d::prelude::QueryFilter;
 use bevy_transform::components::Transform;
 use rand::Rng;
 
+use super::cycle_buttons;
+use super::obstructed;
 use super::pathfind::set_move;
 use super::pathfind::HasPath;
 use super::pathfind::PathfindEvent;
@@ -21,10 +22,14 @@ use super::target_closest_system;
 use super::update_target_system;
 use super::Ai;
 use super::AiTarget;
-use crate::ability::AbilityId;
+use crate::ability::magazine::Magazines;
+use crate::ability::tree::AbilityChoice;
+use crate::ability::SideEnum;
+use crate::ability::Slot;
 use crate::level::Floor;
 use crate::movement::DesiredMove;
 use crate::multiplayer::Action;
+use crate::multiplayer::Buttons;
 use crate::player::Abilities;
 use crate::player::AbilityIds;
 use crate::AbilityOffset;
@@ -32,7 +37,6 @@ use crate::Ally;
 use crate::Enemy;
 use crate::Faction;
 use crate::To2d;
-use crate::To3d;
 
 #[derive(Component)]
 pub struct ChargeAi {
@@ -64,7 +68,7 @@ impl Default for ChargeAi {
             intelligence: 1.0,
             gun_obstruction: true,
             ability_ids: AbilityIds {
-                left_arm: AbilityId::from("gun"),
+                left_arm: AbilityChoice::from("gun"),
                 ..Default::default()
             },
         }
@@ -92,42 +96,50 @@ fn check_obstructions<T: Faction>(
     wall_q: Query<(), With<Floor>>,
     friend_q: Query<(), With<T>>,
 ) {
-    // Filter for friends or walls; we don't want to shoot them. But we ignore
-    // self, as the ray will start inside our collider.
-    let exclude_walls_friends = |this, target, entity| {
-        if entity == this || entity == target {
-            false
-        } else {
-            wall_q.get(entity).is_ok() || friend_q.get(entity).is_ok()
-        }
-    };
-
     for (entity, target, transform, ability_offset, mut ai) in &mut ai_q {
-        let Some(target_entity) = target.entity else {
-            ai.gun_obstruction = true;
-            continue;
-        };
-        let pred = |e| exclude_walls_friends(entity, target_entity, e);
-        let filter = QueryFilter::new().predicate(&pred);
-        let origin = transform.translation + ability_offset.to_vec();
-
-        let dir = target.loc.0 - transform.translation.to_2d();
-
-        let ray = rapier_context.cast_ray(origin, dir.to_3d(0.0), 1.0, true, filter);
-
-        ai.gun_obstruction = ray.is_some();
+        ai.gun_obstruction = obstructed(
+            &rapier_context,
+            &wall_q,
+            &friend_q,
+            entity,
+            target,
+            transform,
+            ability_offset,
+        );
     }
 }
 
-fn gun_system(mut commands: Commands, mut ai_q: Query<(Entity, &ChargeAi, &Abilities)>) {
-    for (entity, ai, abilities) in ai_q.iter_mut() {
-        if !ai.gun_obstruction {
-            // We'll just try to fire all abilities here, as dumb as that is.
-            // Because shooting is first, and cooldowns exist, we'll shoot until
-            // out of ammo, then reload, then repeat, so it's not tooo bad in
-            // practice.
-            Action::all_flags().fire_abilities(&mut commands, entity, abilities);
+fn gun_system(
+    mut commands: Commands,
+    mut ai_q: Query<(
+        Entity,
+        &ChargeAi,
+        &Abilities,
+        &mut Buttons,
+        Option<&Magazines>,
+    )>,
+) {
+    for (entity, ai, abilities, mut buttons, magazines) in ai_q.iter_mut() {
+        if ai.gun_obstruction {
+            buttons.update(Action::none());
+        } else {
+            // We'll just try to fire all abilities here, as dumb as that is,
+            // except for reloading a magazine that still has rounds in it.
+            let mut action = Action::all_flags();
+            for side in [SideEnum::Left, SideEnum::Right] {
+                let loaded = magazines
+                    .and_then(|magazines| magazines.get(Slot::Arm(side)))
+                    .is_some_and(|magazine| !magazine.is_empty());
+                if loaded {
+                    if let Some(secondary) = Slot::ArmSecondary(side).action() {
+                        action &= !secondary;
+                    }
+                }
+            }
+            let action = cycle_buttons(&buttons, action);
+            buttons.update(action);
         }
+        buttons.fire_abilities(&mut commands, entity, abilities);
     }
 }
 
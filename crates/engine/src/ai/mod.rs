@@ -9,6 +9,8 @@ use bevy_ecs::schedule::IntoSystemConfigs;
 use bevy_ecs::schedule::SystemConfigs;
 use bevy_ecs::system::Query;
 use bevy_ecs::system::Res;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_rapier3d::prelude::QueryFilter;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::Transform;
 use pathfind::HasPath;
@@ -16,22 +18,43 @@ use pathfind::HasPath;
 use crate::ability::gun::GunProps;
 use crate::ability::gun::StandardGun;
 use crate::face;
+use crate::level::Floor;
+use crate::multiplayer::Action;
+use crate::multiplayer::Buttons;
+use crate::AbilityOffset;
 use crate::Faction;
 use crate::Target;
 use crate::To2d;
+use crate::To3d;
 
 pub mod charge;
 pub mod pathfind;
+pub mod turret;
 
 pub fn systems() -> SystemConfigs {
     (
         pathfind::poll_pathfinding_system,
         charge::system_set(),
+        turret::system_set(),
         pathfind::pathfinding_system,
     )
         .chain()
 }
 
+/// How many frames an ai holds a button before letting go of it.
+const AI_HOLD_FRAMES: u32 = 30;
+
+/// The buttons to hold this frame, when an ai wants `wanted` held.
+///
+/// We let go of anything held for long enough, so abilities that fire on press
+/// or on release still get used.
+fn cycle_buttons(buttons: &Buttons, wanted: Action) -> Action {
+    (0..16)
+        .map(|i| Action::from(1 << i))
+        .filter(|&action| wanted.contains(action) && buttons.held_for(action) < AI_HOLD_FRAMES)
+        .fold(Action::none(), |acc, action| acc | action)
+}
+
 pub trait Ai: Component {
     /// A measure of how "smart" this ai is, from 0.0 to 1.0.
     fn intelligence(&self) -> f32;
@@ -98,3 +121,36 @@ fn update_target_system<T: Faction, A: Ai>(
         target.loc.0 = lead_translation;
     }
 }
+
+/// Whether there is a wall or friend between this entity and where it wants to
+/// shoot; we don't want to shoot them.
+fn obstructed<T: Faction>(
+    rapier_context: &ReadDefaultRapierContext,
+    wall_q: &Query<(), With<Floor>>,
+    friend_q: &Query<(), With<T>>,
+    entity: Entity,
+    target: &AiTarget,
+    transform: &Transform,
+    ability_offset: &AbilityOffset,
+) -> bool {
+    let Some(target_entity) = target.entity else {
+        return true;
+    };
+
+    // We ignore self, as the ray will start inside our collider.
+    let pred = |e| {
+        if e == entity || e == target_entity {
+            false
+        } else {
+            wall_q.get(e).is_ok() || friend_q.get(e).is_ok()
+        }
+    };
+    let filter = QueryFilter::new().predicate(&pred);
+    let origin = transform.translation + ability_offset.to_vec();
+
+    let dir = target.loc.0 - transform.translation.to_2d();
+
+    let ray = rapier_context.cast_ray(origin, dir.to_3d(0.0), 1.0, true, filter);
+
+    ray.is_some()
+}
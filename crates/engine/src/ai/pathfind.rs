@@ -42,26 +42,33 @@ pub struct PathfindPlugin;
 impl Plugin for PathfindPlugin {
     fn build(&self, app: &mut bevy_app::App) {
         let level_props: &LevelProps = app.world().get_resource().unwrap();
-        let extents = level_props.x.max(level_props.z);
-
-        app.add_plugins(OxidizedNavigationPlugin::<Collider>::new(NavMeshSettings {
-            cell_width: PLAYER_R * 0.5,
-            cell_height: 0.06,
-            tile_width: 100,
-            world_half_extents: extents * 0.5,
-            world_bottom_bound: DEATH_Y,
-            max_traversable_slope_radians: 1.0,
-            walkable_height: u16::MAX,
-            walkable_radius: 2,
-            step_height: 1,
-            min_region_area: 100,
-            max_edge_length: 80,
-            max_contour_simplification_error: 1.1,
-            max_tile_generation_tasks: NonZeroU16::new(1),
-            max_region_area_to_merge_into: 500,
-        }))
-        .insert_resource(PathfindingTasks::default())
-        .add_event::<PathfindEvent>();
+        let settings = nav_mesh_settings(level_props);
+
+        app.add_plugins(OxidizedNavigationPlugin::<Collider>::new(settings))
+            .insert_resource(PathfindingTasks::default())
+            .add_event::<PathfindEvent>();
+    }
+}
+
+/// The navmesh settings for a level of the given size.
+pub fn nav_mesh_settings(level_props: &LevelProps) -> NavMeshSettings {
+    let extents = level_props.x.max(level_props.z);
+
+    NavMeshSettings {
+        cell_width: PLAYER_R * 0.5,
+        cell_height: 0.06,
+        tile_width: 100,
+        world_half_extents: extents * 0.5,
+        world_bottom_bound: DEATH_Y,
+        max_traversable_slope_radians: 1.0,
+        walkable_height: u16::MAX,
+        walkable_radius: 2,
+        step_height: 1,
+        min_region_area: 100,
+        max_edge_length: 80,
+        max_contour_simplification_error: 1.1,
+        max_tile_generation_tasks: NonZeroU16::new(1),
+        max_region_area_to_merge_into: 500,
     }
 }
 
@@ -0,0 +1,85 @@
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::With;
+use bevy_ecs::schedule::IntoSystemConfigs;
+use bevy_ecs::schedule::SystemConfigs;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::Query;
+use bevy_rapier3d::plugin::ReadDefaultRapierContext;
+use bevy_transform::components::Transform;
+
+use super::cycle_buttons;
+use super::obstructed;
+use super::target_closest_system;
+use super::update_target_system;
+use super::Ai;
+use super::AiTarget;
+use crate::level::Floor;
+use crate::multiplayer::Action;
+use crate::multiplayer::Buttons;
+use crate::player::Abilities;
+use crate::AbilityOffset;
+use crate::Ally;
+use crate::Enemy;
+use crate::Faction;
+
+/// An ai that stays put, and shoots the closest foe it can see.
+#[derive(Component, Default)]
+pub struct TurretAi;
+
+impl Ai for TurretAi {
+    fn intelligence(&self) -> f32 {
+        0.5
+    }
+}
+
+pub fn system_set() -> SystemConfigs {
+    (
+        target_closest_system::<Enemy, TurretAi>,
+        target_closest_system::<Ally, TurretAi>,
+        update_target_system::<Enemy, TurretAi>,
+        update_target_system::<Ally, TurretAi>,
+        fire_system::<Enemy>,
+        fire_system::<Ally>,
+    )
+        .chain()
+}
+
+fn fire_system<T: Faction>(
+    mut commands: Commands,
+    rapier_context: ReadDefaultRapierContext,
+    mut ai_q: Query<
+        (
+            Entity,
+            &AiTarget,
+            &Transform,
+            &AbilityOffset,
+            &Abilities,
+            &mut Buttons,
+        ),
+        (With<T>, With<TurretAi>),
+    >,
+    wall_q: Query<(), With<Floor>>,
+    friend_q: Query<(), With<T>>,
+) {
+    for (entity, target, transform, ability_offset, abilities, mut buttons) in &mut ai_q {
+        let obstructed = obstructed(
+            &rapier_context,
+            &wall_q,
+            &friend_q,
+            entity,
+            target,
+            transform,
+            ability_offset,
+        );
+        if obstructed {
+            buttons.update(Action::none());
+        } else {
+            // Like the charge ai, we just hold everything down and let
+            // cooldowns sort it out.
+            let action = cycle_buttons(&buttons, Action::all_flags());
+            buttons.update(action);
+        }
+        buttons.fire_abilities(&mut commands, entity, abilities);
+    }
+}
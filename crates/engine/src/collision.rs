@@ -3,8 +3,13 @@ use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::event::EventReader;
 use bevy_ecs::system::Query;
+use bevy_math::Quat;
+use bevy_math::Vec3;
 use bevy_rapier3d::prelude::ActiveEvents;
+use bevy_rapier3d::prelude::Collider;
 use bevy_rapier3d::prelude::CollisionEvent;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_rapier3d::prelude::RapierContext;
 use smallvec::SmallVec;
 
 #[derive(Bundle)]
@@ -78,3 +83,23 @@ pub fn collision_system(
         };
     }
 }
+
+/// Everything intersecting `shape` at `position`, sorted and without repeats.
+///
+/// Rapier doesn't promise an order, and we need to be deterministic.
+pub fn sorted_intersections(
+    rapier_context: &RapierContext,
+    position: Vec3,
+    rotation: Quat,
+    shape: &Collider,
+    filter: QueryFilter,
+) -> Vec<Entity> {
+    let mut targets = Vec::new();
+    rapier_context.intersections_with_shape(position, rotation, shape, filter, |target| {
+        targets.push(target);
+        true
+    });
+    targets.sort();
+    targets.dedup();
+    targets
+}
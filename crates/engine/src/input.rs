@@ -11,6 +11,7 @@ use bevy_transform::components::Transform;
 use crate::face;
 use crate::movement::DesiredMove;
 use crate::multiplayer::Action;
+use crate::multiplayer::Buttons;
 use crate::multiplayer::PlayerInputs;
 use crate::player::Abilities;
 use crate::AppState;
@@ -22,6 +23,7 @@ use crate::Target;
 pub struct InputUser {
     entity: Entity,
     abilities: &'static Abilities,
+    buttons: &'static mut Buttons,
     player: &'static Player,
     transform: &'static mut Transform,
     target: &'static mut Target,
@@ -44,8 +46,9 @@ pub fn apply_inputs(
             face(&mut user.transform, cursor);
         }
         // Abilities
-        let buttons = input.buttons();
-        buttons.fire_abilities(&mut commands, user.entity, user.abilities);
+        user.buttons.update(input.buttons());
+        user.buttons
+            .fire_abilities(&mut commands, user.entity, user.abilities);
 
         // Movement
         user.desired_move.dir = input.movement().clamp_length_max(1.0);
@@ -65,7 +68,7 @@ pub fn pause_resume(
 
         if input.buttons().contains(Action::Menu) {
             match state.get() {
-                AppState::Loading => {}
+                AppState::Loading | AppState::Editor => {}
                 AppState::Running => {
                     next_state.set(AppState::Menu);
                 }
@@ -0,0 +1,362 @@
+//! Level elements that move or react; doors, switches, pressure plates,
+//! platforms and elevators.
+//!
+//! Triggers (pressure plates, switches, and clearing the stage) activate
+//! channels, and mechanisms (doors and elevators) move while their channel is
+//! active. Platforms ignore channels, and travel back and forth forever.
+//!
+//! Everything here moves a whole number of frames at a time, so every client
+//! agrees on where it is.
+
+use bevy_ecs::component::Component;
+use bevy_ecs::query::With;
+use bevy_ecs::query::Without;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::ResMut;
+use bevy_ecs::system::Resource;
+use bevy_math::Vec3;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::Friction;
+use bevy_rapier3d::prelude::RigidBody;
+use bevy_rapier3d::prelude::Sensor;
+use bevy_transform::components::GlobalTransform;
+use bevy_transform::components::Transform;
+use oxidized_navigation::NavMeshAffector;
+
+use super::Cell;
+use super::InLevel;
+use super::LevelGrid;
+use super::SHORT_WALL;
+use super::WALL_HEIGHT;
+use crate::collision::TrackCollisionBundle;
+use crate::collision::TrackCollisions;
+use crate::lifecycle::DEATH_Y;
+use crate::run::Run;
+use crate::run::RunState;
+use crate::status_effect::StatusBundle;
+use crate::status_effect::StatusProps;
+use crate::time::FREQUENCY;
+use crate::CharacterMarker;
+use crate::Health;
+use crate::Libm;
+use crate::Shootable;
+
+/// This channel is active once the current stage has been cleared.
+pub const OBJECTIVE_CHANNEL: u8 = 255;
+
+const DOOR_FRAMES: u32 = 32;
+const ELEVATOR_FRAMES: u32 = 96;
+/// Platform speed, in units per second.
+const PLATFORM_SPEED: f32 = 1.0;
+const PLATFORM_THICKNESS: f32 = 0.1;
+const PLATE_THICKNESS: f32 = 0.05;
+// Switches only care that they've been hit, so this just needs to survive any
+// single frame of damage.
+const SWITCH_HEALTH: f32 = 1000.0;
+
+/// Which channels are active this frame.
+#[derive(Resource, Debug)]
+pub struct Channels {
+    active: [bool; 256],
+}
+
+impl Default for Channels {
+    fn default() -> Self {
+        Self {
+            active: [false; 256],
+        }
+    }
+}
+
+impl Channels {
+    pub fn is_active(&self, channel: u8) -> bool {
+        self.active[channel as usize]
+    }
+
+    fn activate(&mut self, channel: u8) {
+        self.active[channel as usize] = true;
+    }
+}
+
+/// Activates its channel while a character stands on it.
+#[derive(Component, Debug)]
+pub struct PressurePlate {
+    pub channel: u8,
+    pub pressed: bool,
+}
+
+/// Toggles its channel whenever it's shot.
+#[derive(Component, Debug)]
+pub struct Switch {
+    pub channel: u8,
+    pub on: bool,
+    /// Whether it took damage last frame, so that steady damage, like a laser
+    /// or burning, only counts as one hit.
+    hit: bool,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum MoverKind {
+    /// Sinks into the floor while its channel is active.
+    Door,
+    /// Rises while its channel is active.
+    Elevator,
+    /// Travels back and forth between two points.
+    Platform,
+}
+
+/// A piece of the level that moves between two locations.
+#[derive(Component, Debug)]
+pub struct Mover {
+    pub kind: MoverKind,
+    pub channel: u8,
+    /// The size of the mover's collider.
+    pub dim: Vec3,
+    from: Vec3,
+    to: Vec3,
+    /// How many frames we've moved from `from` towards `to`.
+    progress: u32,
+    /// How many frames it takes to get from `from` to `to`.
+    frames: u32,
+    /// Only used by platforms.
+    forward: bool,
+    /// How far we moved last frame; whatever's on top moves with us.
+    delta: Vec3,
+}
+
+impl Mover {
+    fn new(kind: MoverKind, channel: u8, dim: Vec3, from: Vec3, to: Vec3, frames: u32) -> Self {
+        Self {
+            kind,
+            channel,
+            dim,
+            from,
+            to,
+            progress: 0,
+            frames: frames.max(1),
+            forward: true,
+            delta: Vec3::ZERO,
+        }
+    }
+
+    /// How far along we are, from 0 (at the start) to 1 (at the end).
+    pub fn fraction(&self) -> f32 {
+        self.progress as f32 / self.frames as f32
+    }
+
+    fn location(&self) -> Vec3 {
+        self.from.lerp(self.to, self.fraction())
+    }
+
+    fn step(&mut self, channels: &Channels) {
+        let forward = match self.kind {
+            MoverKind::Door | MoverKind::Elevator => channels.is_active(self.channel),
+            MoverKind::Platform => {
+                if self.progress == 0 {
+                    self.forward = true;
+                } else if self.progress == self.frames {
+                    self.forward = false;
+                }
+                self.forward
+            }
+        };
+
+        if forward {
+            self.progress = (self.progress + 1).min(self.frames);
+        } else {
+            self.progress = self.progress.saturating_sub(1);
+        }
+    }
+}
+
+fn spawn_mover(commands: &mut Commands, mover: Mover) {
+    commands.spawn((
+        RigidBody::KinematicPositionBased,
+        Collider::cuboid(mover.dim.x * 0.5, mover.dim.y * 0.5, mover.dim.z * 0.5),
+        Transform::from_translation(mover.from),
+        GlobalTransform::default(),
+        Friction::default(),
+        TrackCollisionBundle::on(),
+        InLevel,
+        Shootable,
+        NavMeshAffector,
+        mover,
+    ));
+}
+
+fn platform_frames(from: Vec3, to: Vec3) -> u32 {
+    Libm::ceil(from.distance(to) / PLATFORM_SPEED * FREQUENCY) as u32
+}
+
+/// Spawn every mechanism in the grid.
+///
+/// Note: This only spawns the moving parts; the static floor beneath them is
+/// spawned with the rest of the level.
+pub(super) fn spawn(grid: &LevelGrid, commands: &mut Commands) {
+    let pixel = LevelGrid::PIXEL;
+
+    for (x, z, cell) in grid.cells() {
+        let center = grid.cell_center(x, z);
+        match cell {
+            Cell::Door(channel) => {
+                let dim = Vec3::new(pixel, WALL_HEIGHT, pixel);
+                let from = center + Vec3::new(0.0, WALL_HEIGHT * 0.5, 0.0);
+                let to = from - Vec3::new(0.0, WALL_HEIGHT, 0.0);
+                let mover = Mover::new(MoverKind::Door, channel, dim, from, to, DOOR_FRAMES);
+                spawn_mover(commands, mover);
+            }
+            Cell::ShortElevator(channel) | Cell::Elevator(channel) => {
+                let rise = if cell == Cell::ShortElevator(channel) {
+                    SHORT_WALL
+                } else {
+                    WALL_HEIGHT
+                };
+                let dim = Vec3::new(pixel, -DEATH_Y, pixel);
+                let from = center + Vec3::new(0.0, DEATH_Y * 0.5, 0.0);
+                let to = from + Vec3::new(0.0, rise, 0.0);
+                let frames = Libm::ceil(ELEVATOR_FRAMES as f32 * rise / WALL_HEIGHT) as u32;
+                let mover = Mover::new(MoverKind::Elevator, channel, dim, from, to, frames);
+                spawn_mover(commands, mover);
+            }
+            Cell::Platform(channel) => {
+                let offset = Vec3::new(0.0, -PLATFORM_THICKNESS * 0.5, 0.0);
+                let from = center + offset;
+                let end = grid
+                    .cells()
+                    .find(|(_, _, cell)| *cell == Cell::PlatformEnd(channel));
+                let to = match end {
+                    Some((x, z, _)) => grid.cell_center(x, z) + offset,
+                    None => {
+                        tracing::warn!(channel, "Platform has no end; it won't move");
+                        from
+                    }
+                };
+                let dim = Vec3::new(pixel, PLATFORM_THICKNESS, pixel);
+                let frames = platform_frames(from, to);
+                let mover = Mover::new(MoverKind::Platform, channel, dim, from, to, frames);
+                spawn_mover(commands, mover);
+            }
+            Cell::PressurePlate(channel) => {
+                commands.spawn((
+                    Collider::cuboid(pixel * 0.5, PLATE_THICKNESS * 0.5, pixel * 0.5),
+                    Transform::from_translation(
+                        center + Vec3::new(0.0, PLATE_THICKNESS * 0.5, 0.0),
+                    ),
+                    GlobalTransform::default(),
+                    Sensor,
+                    TrackCollisionBundle::on(),
+                    InLevel,
+                    PressurePlate {
+                        channel,
+                        pressed: false,
+                    },
+                ));
+            }
+            Cell::Switch(channel) => {
+                commands.spawn((
+                    RigidBody::Fixed,
+                    Collider::cuboid(pixel * 0.5, SHORT_WALL * 0.5, pixel * 0.5),
+                    Transform::from_translation(center + Vec3::new(0.0, SHORT_WALL * 0.5, 0.0)),
+                    GlobalTransform::default(),
+                    Health::new(SWITCH_HEALTH),
+                    StatusBundle::from(StatusProps {
+                        thermal_mass: 1.0,
+                        capacitance: 1.0,
+                    }),
+                    InLevel,
+                    Shootable,
+                    NavMeshAffector,
+                    Switch {
+                        channel,
+                        on: false,
+                        hit: false,
+                    },
+                ));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Flip any switch that's taken damage since last frame, but wasn't the frame
+/// before.
+pub fn switch_system(mut query: Query<(&mut Switch, &mut Health)>) {
+    for (mut switch, mut health) in &mut query {
+        let hit = health.cur < health.max;
+        if hit && !switch.hit {
+            switch.on = !switch.on;
+        }
+        switch.hit = hit;
+        health.cur = health.max;
+    }
+}
+
+/// Work out which channels are active this frame.
+pub fn channel_system(
+    mut channels: ResMut<Channels>,
+    run: Res<Run>,
+    mut plate_q: Query<(&mut PressurePlate, &TrackCollisions)>,
+    switch_q: Query<&Switch>,
+    character_q: Query<(), With<CharacterMarker>>,
+) {
+    *channels = Channels::default();
+
+    if matches!(run.state(), RunState::Transitioning(_) | RunState::Won) {
+        channels.activate(OBJECTIVE_CHANNEL);
+    }
+
+    for (mut plate, collisions) in &mut plate_q {
+        plate.pressed = collisions
+            .targets
+            .iter()
+            .any(|&target| character_q.contains(target));
+        if plate.pressed {
+            channels.activate(plate.channel);
+        }
+    }
+
+    for switch in &switch_q {
+        if switch.on {
+            channels.activate(switch.channel);
+        }
+    }
+}
+
+pub fn move_system(channels: Res<Channels>, mut query: Query<(&mut Mover, &mut Transform)>) {
+    for (mut mover, mut transform) in &mut query {
+        let before = mover.location();
+        mover.step(&channels);
+        let after = mover.location();
+
+        mover.delta = after - before;
+        transform.translation = after;
+    }
+}
+
+/// Carry characters standing on platforms and elevators along with them.
+///
+/// We only need to carry them sideways; physics takes care of pushing them up,
+/// and gravity brings them down.
+pub fn carry_system(
+    mover_q: Query<(&Mover, &Transform, &TrackCollisions)>,
+    mut character_q: Query<&mut Transform, (With<CharacterMarker>, Without<Mover>)>,
+) {
+    for (mover, mover_transform, collisions) in &mover_q {
+        let delta = mover.delta.with_y(0.0);
+        if delta == Vec3::ZERO {
+            continue;
+        }
+        let top = mover_transform.translation.y + mover.dim.y * 0.5;
+        for &target in &collisions.targets {
+            let Ok(mut transform) = character_q.get_mut(target) else {
+                continue;
+            };
+            // Only carry those on top, not those we bump into.
+            if transform.translation.y > top {
+                transform.translation += delta;
+            }
+        }
+    }
+}
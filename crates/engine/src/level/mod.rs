@@ -0,0 +1,513 @@
+use std::path::Path;
+use std::path::PathBuf;
+
+use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::With;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::ResMut;
+use bevy_ecs::system::Resource;
+use bevy_hierarchy::DespawnRecursiveExt;
+use bevy_math::Vec3;
+use bevy_rapier3d::prelude::Collider;
+use bevy_rapier3d::prelude::Friction;
+use bevy_rapier3d::prelude::QueryFilter;
+use bevy_rapier3d::prelude::RapierContext;
+use bevy_rapier3d::prelude::RigidBody;
+use bevy_transform::components::GlobalTransform;
+use bevy_transform::components::Transform;
+use oxidized_navigation::NavMeshAffector;
+use rand::rngs::StdRng;
+use rand::Rng;
+use rand::SeedableRng;
+
+use crate::lifecycle::DEATH_Y;
+use crate::Libm;
+use crate::Shootable;
+use crate::PLAYER_R;
+
+pub mod mechanism;
+pub mod validate;
+
+/// A market to indicate that an entity is part of a level, and should be
+/// deleted when it ends.
+#[derive(Component, Default)]
+pub struct InLevel;
+
+pub fn clear_level(mut commands: Commands, query: Query<Entity, With<InLevel>>) {
+    despawn_level(&mut commands, &query);
+}
+
+pub fn despawn_level(commands: &mut Commands, query: &Query<Entity, With<InLevel>>) {
+    for entity in query {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+pub const WALL_HEIGHT: f32 = 0.6;
+const WALL_WIDTH: f32 = 0.3;
+pub const SHORT_WALL: f32 = 0.25;
+
+#[derive(Resource, Debug)]
+pub struct LevelProps {
+    pub x: f32,
+    pub z: f32,
+    pub player_spawns: Vec<Vec3>,
+    pub enemy_spawns: Vec<Vec3>,
+}
+
+impl Default for LevelProps {
+    fn default() -> Self {
+        Self {
+            x: 15.0,
+            z: 15.0,
+            player_spawns: Vec::new(),
+            enemy_spawns: Vec::new(),
+        }
+    }
+}
+
+impl LevelProps {
+    /// Where the player with the given index should spawn.
+    pub fn player_spawn(&self, index: usize) -> Vec3 {
+        if self.player_spawns.is_empty() {
+            Vec3::ZERO
+        } else {
+            self.player_spawns[index % self.player_spawns.len()]
+        }
+    }
+
+    /// Where an enemy should spawn; one of the level's spawn points if it has
+    /// any, and anywhere otherwise.
+    pub fn enemy_spawn(&self, rapier_context: &RapierContext) -> Vec3 {
+        if self.enemy_spawns.is_empty() {
+            self.point_in_plane(rapier_context)
+        } else {
+            let idx = rand::rng().random_range(0..self.enemy_spawns.len());
+            self.enemy_spawns[idx]
+        }
+    }
+
+    pub fn point_in_plane(&self, rapier_context: &RapierContext) -> Vec3 {
+        let mut rng = rand::rng();
+        let filter = QueryFilter::default();
+        loop {
+            let x = rng.random::<f32>() * (self.x - PLAYER_R) - (self.x - PLAYER_R) * 0.5;
+            let z = rng.random::<f32>() * (self.z - PLAYER_R) - (self.z - PLAYER_R) * 0.5;
+            let loc = Vec3::new(x, 0.0, z);
+
+            let ray_loc = loc + Vec3::new(0.0, 0.1, 0.0);
+            let y_ray = rapier_context.cast_ray(ray_loc, -Vec3::Y, 0.2, true, filter);
+            let other_rays = [
+                rapier_context.cast_ray(ray_loc, Vec3::X, PLAYER_R, true, filter),
+                rapier_context.cast_ray(ray_loc, -Vec3::X, PLAYER_R, true, filter),
+                rapier_context.cast_ray(ray_loc, Vec3::Z, PLAYER_R, true, filter),
+                rapier_context.cast_ray(ray_loc, -Vec3::Z, PLAYER_R, true, filter),
+            ];
+
+            if let Some((_entity, toi)) = y_ray {
+                if toi > 0.0 && other_rays.into_iter().all(|r| r.is_none()) {
+                    // There's ground beneath us, and we're not in a wall or the
+                    // player!
+                    return loc;
+                }
+            }
+        }
+    }
+}
+
+struct FloorSpawner {
+    dim: Vec3,
+    loc: Vec3,
+}
+
+#[derive(Component)]
+pub struct Floor {
+    pub dim: Vec3,
+    pub loc: Vec3,
+}
+
+impl FloorSpawner {
+    fn new(dim: Vec3, loc: Vec3) -> Self {
+        Self { dim, loc }
+    }
+
+    fn spawn(self, commands: &mut Commands) {
+        commands.spawn((
+            RigidBody::Fixed,
+            Collider::cuboid(self.dim.x * 0.5, self.dim.y * 0.5, self.dim.z * 0.5),
+            Transform::from_translation(self.loc),
+            GlobalTransform::default(),
+            Friction::default(),
+            InLevel,
+            Shootable,
+            Floor {
+                dim: self.dim,
+                loc: self.loc,
+            },
+            NavMeshAffector,
+        ));
+    }
+}
+
+const PIT_COLOR: [u8; 3] = [0, 0, 0];
+const FLOOR_COLOR: [u8; 3] = [150, 240, 110];
+const WALL_COLOR: [u8; 3] = [220, 110, 165];
+const SHORT_WALL_COLOR: [u8; 3] = [255, 200, 255];
+const PLAYER_SPAWN_COLOR: [u8; 3] = [80, 170, 255];
+const ENEMY_SPAWN_COLOR: [u8; 3] = [255, 90, 50];
+// Mechanisms use red and green for their kind, and blue for their channel.
+const DOOR_COLOR: [u8; 2] = [200, 120];
+const PRESSURE_PLATE_COLOR: [u8; 2] = [120, 200];
+const SWITCH_COLOR: [u8; 2] = [250, 250];
+const PLATFORM_COLOR: [u8; 2] = [60, 60];
+const PLATFORM_END_COLOR: [u8; 2] = [60, 30];
+const SHORT_ELEVATOR_COLOR: [u8; 2] = [90, 60];
+const ELEVATOR_COLOR: [u8; 2] = [90, 90];
+
+pub const TEST_LEVEL: &str = "assets/levels/test2.png";
+
+/// A single cell of a level; one pixel of a level image.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Cell {
+    Pit,
+    Floor,
+    ShortWall,
+    Wall,
+    /// Floor, where players spawn.
+    PlayerSpawn,
+    /// Floor, where enemies spawn.
+    EnemySpawn,
+    /// A wall that sinks into the floor while its channel is active.
+    Door(u8),
+    /// Floor that activates its channel while a character stands on it.
+    PressurePlate(u8),
+    /// A short pillar that toggles its channel when shot.
+    Switch(u8),
+    /// A platform over a pit, travelling back and forth to the `PlatformEnd`
+    /// with the same channel.
+    Platform(u8),
+    /// A pit, where the `Platform` with the same channel turns around.
+    PlatformEnd(u8),
+    /// A pit, with an elevator that rises to `SHORT_WALL` while its channel is
+    /// active.
+    ShortElevator(u8),
+    /// A pit, with an elevator that rises to `WALL_HEIGHT` while its channel
+    /// is active.
+    Elevator(u8),
+    /// A color we don't know what to do with.
+    Unknown([u8; 3]),
+}
+
+impl Cell {
+    pub fn from_color(color: [u8; 3]) -> Self {
+        match color {
+            PIT_COLOR => Cell::Pit,
+            FLOOR_COLOR => Cell::Floor,
+            SHORT_WALL_COLOR => Cell::ShortWall,
+            WALL_COLOR => Cell::Wall,
+            PLAYER_SPAWN_COLOR => Cell::PlayerSpawn,
+            ENEMY_SPAWN_COLOR => Cell::EnemySpawn,
+            [r, g, channel] => match [r, g] {
+                DOOR_COLOR => Cell::Door(channel),
+                PRESSURE_PLATE_COLOR => Cell::PressurePlate(channel),
+                SWITCH_COLOR => Cell::Switch(channel),
+                PLATFORM_COLOR => Cell::Platform(channel),
+                PLATFORM_END_COLOR => Cell::PlatformEnd(channel),
+                SHORT_ELEVATOR_COLOR => Cell::ShortElevator(channel),
+                ELEVATOR_COLOR => Cell::Elevator(channel),
+                _ => Cell::Unknown(color),
+            },
+        }
+    }
+
+    pub fn color(self) -> [u8; 3] {
+        match self {
+            Cell::Pit => PIT_COLOR,
+            Cell::Floor => FLOOR_COLOR,
+            Cell::ShortWall => SHORT_WALL_COLOR,
+            Cell::Wall => WALL_COLOR,
+            Cell::PlayerSpawn => PLAYER_SPAWN_COLOR,
+            Cell::EnemySpawn => ENEMY_SPAWN_COLOR,
+            Cell::Door(channel) => channel_color(DOOR_COLOR, channel),
+            Cell::PressurePlate(channel) => channel_color(PRESSURE_PLATE_COLOR, channel),
+            Cell::Switch(channel) => channel_color(SWITCH_COLOR, channel),
+            Cell::Platform(channel) => channel_color(PLATFORM_COLOR, channel),
+            Cell::PlatformEnd(channel) => channel_color(PLATFORM_END_COLOR, channel),
+            Cell::ShortElevator(channel) => channel_color(SHORT_ELEVATOR_COLOR, channel),
+            Cell::Elevator(channel) => channel_color(ELEVATOR_COLOR, channel),
+            Cell::Unknown(color) => color,
+        }
+    }
+
+    /// The height of the top of the static part of this cell, or `None` if
+    /// it's a pit.
+    ///
+    /// Note: Mechanisms are spawned separately, on top of this.
+    pub fn height(self) -> Option<f32> {
+        match self {
+            Cell::Pit
+            | Cell::Platform(_)
+            | Cell::PlatformEnd(_)
+            | Cell::ShortElevator(_)
+            | Cell::Elevator(_) => None,
+            Cell::Floor
+            | Cell::PlayerSpawn
+            | Cell::EnemySpawn
+            | Cell::Door(_)
+            | Cell::PressurePlate(_)
+            | Cell::Switch(_) => Some(0.0),
+            Cell::ShortWall => Some(SHORT_WALL),
+            Cell::Wall => Some(WALL_HEIGHT),
+            Cell::Unknown(_) => Some(WALL_HEIGHT * 2.0),
+        }
+    }
+}
+
+fn channel_color(color: [u8; 2], channel: u8) -> [u8; 3] {
+    [color[0], color[1], channel]
+}
+
+/// A level, as a grid of cells.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelGrid {
+    width: u32,
+    height: u32,
+    cells: Vec<Cell>,
+}
+
+impl LevelGrid {
+    // TODO: This is currently a bit larger than PLAYER_R to give the ai some
+    // extra pathfinding room. But we should pathfind better instead.
+    pub const PIXEL: f32 = 0.30;
+
+    pub fn new(width: u32, height: u32, fill: Cell) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![fill; (width * height) as usize],
+        }
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> image::ImageResult<Self> {
+        let image = image::ImageReader::open(path)?.decode()?.into_rgb8();
+        let (width, height) = image.dimensions();
+        let cells = image
+            .pixels()
+            .map(|color| Cell::from_color(color.0))
+            .collect();
+        Ok(Self {
+            width,
+            height,
+            cells,
+        })
+    }
+
+    /// Generate a walled-in level with some random obstacles.
+    ///
+    /// The same seed always gives the same level, so every client can generate
+    /// it themselves.
+    pub fn generate(seed: u64, width: u32, height: u32) -> Self {
+        // Leave some room in the middle, where players spawn.
+        const CLEAR_RADIUS: i64 = 4;
+
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut grid = Self::new(width, height, Cell::Floor);
+        for z in 0..height {
+            for x in 0..width {
+                let cell = if x == 0 || z == 0 || x == width - 1 || z == height - 1 {
+                    Cell::Wall
+                } else {
+                    let dx = x as i64 - width as i64 / 2;
+                    let dz = z as i64 - height as i64 / 2;
+                    if dx * dx + dz * dz <= CLEAR_RADIUS * CLEAR_RADIUS {
+                        continue;
+                    }
+                    match rng.random_range(0..100) {
+                        0..6 => Cell::Wall,
+                        6..10 => Cell::ShortWall,
+                        10..13 => Cell::Pit,
+                        _ => Cell::Floor,
+                    }
+                };
+                grid.set(x, z, cell);
+            }
+        }
+        grid
+    }
+
+    pub fn width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn height(&self) -> u32 {
+        self.height
+    }
+
+    pub fn get(&self, x: u32, z: u32) -> Cell {
+        self.cells[self.index(x, z)]
+    }
+
+    pub fn set(&mut self, x: u32, z: u32, cell: Cell) {
+        let index = self.index(x, z);
+        self.cells[index] = cell;
+    }
+
+    fn index(&self, x: u32, z: u32) -> usize {
+        debug_assert!(x < self.width && z < self.height);
+        (z * self.width + x) as usize
+    }
+
+    /// Iterate over every cell, with its coordinates.
+    pub fn cells(&self) -> impl Iterator<Item = (u32, u32, Cell)> + '_ {
+        self.cells.iter().enumerate().map(|(i, cell)| {
+            let i = i as u32;
+            (i % self.width, i / self.width, *cell)
+        })
+    }
+
+    pub fn props(&self) -> LevelProps {
+        let spawns = |kind| {
+            self.cells()
+                .filter(|(_, _, cell)| *cell == kind)
+                .map(|(x, z, _)| self.cell_center(x, z))
+                .collect()
+        };
+        LevelProps {
+            x: self.x(),
+            z: self.z(),
+            player_spawns: spawns(Cell::PlayerSpawn),
+            enemy_spawns: spawns(Cell::EnemySpawn),
+        }
+    }
+
+    /// The size of the level in the x direction, in world units.
+    fn x(&self) -> f32 {
+        self.width as f32 * Self::PIXEL
+    }
+
+    /// The size of the level in the z direction, in world units.
+    fn z(&self) -> f32 {
+        self.height as f32 * Self::PIXEL
+    }
+
+    /// The location in the world of the center of the given cell, at floor
+    /// level.
+    pub fn cell_center(&self, x: u32, z: u32) -> Vec3 {
+        Vec3::new(
+            -self.x() * 0.5 + x as f32 * Self::PIXEL,
+            0.0,
+            -self.z() * 0.5 + z as f32 * Self::PIXEL,
+        )
+    }
+
+    /// The cell containing the given location, if it's in the level.
+    pub fn cell_at(&self, loc: Vec3) -> Option<(u32, u32)> {
+        let x = Libm::round((loc.x + self.x() * 0.5) / Self::PIXEL);
+        let z = Libm::round((loc.z + self.z() * 0.5) / Self::PIXEL);
+        if x < 0.0 || z < 0.0 || x >= self.width as f32 || z >= self.height as f32 {
+            return None;
+        }
+        Some((x as u32, z as u32))
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> image::ImageResult<()> {
+        let mut image = image::RgbImage::new(self.width, self.height);
+        for (x, z, cell) in self.cells() {
+            image.put_pixel(x, z, image::Rgb(cell.color()));
+        }
+        image.save(path)
+    }
+
+    pub fn spawn(&self, commands: &mut Commands, props: &mut LevelProps) {
+        *props = self.props();
+        let pixel = Self::PIXEL;
+
+        for (x, z, cell) in self.cells() {
+            if let Cell::Unknown(color) = cell {
+                tracing::warn!("Not an acceptable color: {color:?}");
+            }
+            let Some(height) = cell.height() else {
+                continue;
+            };
+            let dim = Vec3::new(pixel, -DEATH_Y + height, pixel);
+            let loc = self.cell_center(x, z) + Vec3::new(0.0, DEATH_Y * 0.5 + height * 0.5, 0.0);
+            FloorSpawner::new(dim, loc).spawn(commands);
+        }
+        mechanism::spawn(self, commands);
+    }
+}
+
+/// Where to get a level from.
+#[derive(Debug, Clone)]
+pub enum LevelSource {
+    Image(PathBuf),
+    /// A level that's already in memory, such as one from the editor.
+    Grid(LevelGrid),
+    Generated {
+        seed: u64,
+        width: u32,
+        height: u32,
+    },
+}
+
+impl LevelSource {
+    pub fn load(&self) -> LevelGrid {
+        match self {
+            LevelSource::Image(path) => LevelGrid::load(path)
+                .unwrap_or_else(|err| panic!("Could not load level {path:?}: {err}")),
+            LevelSource::Grid(grid) => grid.clone(),
+            LevelSource::Generated {
+                seed,
+                width,
+                height,
+            } => LevelGrid::generate(*seed, *width, *height),
+        }
+    }
+}
+
+pub fn test_level(mut commands: Commands, mut props: ResMut<LevelProps>) {
+    LevelGrid::load(TEST_LEVEL)
+        .unwrap()
+        .spawn(&mut commands, &mut props);
+}
+
+pub fn default_level(mut commands: Commands, props: Res<LevelProps>) {
+    let height = WALL_HEIGHT;
+    let width = WALL_WIDTH;
+    let commands = &mut commands;
+    // Floor
+    FloorSpawner::new(
+        Vec3::new(props.x + width, height, props.z + height),
+        Vec3::new(0.0, -height * 0.5, 0.0),
+    )
+    .spawn(commands);
+
+    // Walls
+    let half_wall = height * 0.5;
+    FloorSpawner::new(
+        Vec3::new(props.x + width, height, width),
+        Vec3::new(0.0, half_wall, -props.z * 0.5),
+    )
+    .spawn(commands);
+
+    FloorSpawner::new(
+        Vec3::new(props.x + width, width, height),
+        Vec3::new(0.0, half_wall, props.z * 0.5),
+    )
+    .spawn(commands);
+
+    FloorSpawner::new(
+        Vec3::new(width, height, props.z + width),
+        Vec3::new(-props.x * 0.5, half_wall, 0.0),
+    )
+    .spawn(commands);
+
+    FloorSpawner::new(
+        Vec3::new(width, height, props.z + width),
+        Vec3::new(props.x * 0.5, half_wall, 0.0),
+    )
+    .spawn(commands);
+}
@@ -0,0 +1,275 @@
+//! Find problems with levels before anyone tries to play them.
+
+use std::fmt;
+
+use bevy_math::Vec3;
+use oxidized_navigation::query::find_path;
+use oxidized_navigation::tiles::NavMeshTiles;
+use oxidized_navigation::NavMeshSettings;
+
+use super::Cell;
+use super::LevelGrid;
+
+/// How far from a point we'll look for the navmesh, when pathfinding.
+const SEARCH_RADIUS: f32 = LevelGrid::PIXEL * 2.0;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Problem {
+    pub severity: Severity,
+    pub message: String,
+}
+
+impl Problem {
+    pub fn warning(message: String) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message,
+        }
+    }
+
+    pub fn error(message: String) -> Self {
+        Self {
+            severity: Severity::Error,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "{severity}: {}", self.message)
+    }
+}
+
+/// Whether characters can walk on this cell when the level starts.
+///
+/// Note: Doors start closed, so we don't count them.
+fn walkable(cell: Cell) -> bool {
+    matches!(
+        cell,
+        Cell::Floor | Cell::PlayerSpawn | Cell::EnemySpawn | Cell::PressurePlate(_)
+    )
+}
+
+/// A connected area of walkable cells.
+#[derive(Debug)]
+struct Region {
+    /// The first cell of the region, in row order.
+    start: (u32, u32),
+    size: usize,
+}
+
+fn regions(grid: &LevelGrid) -> Vec<Region> {
+    let mut seen = vec![false; (grid.width() * grid.height()) as usize];
+    let mut regions = Vec::new();
+
+    for (x, z, cell) in grid.cells() {
+        if !walkable(cell) || seen[grid.index(x, z)] {
+            continue;
+        }
+        seen[grid.index(x, z)] = true;
+        let mut size = 0;
+        let mut stack = vec![(x, z)];
+        while let Some((x, z)) = stack.pop() {
+            size += 1;
+            let neighbors = [
+                (x.wrapping_sub(1), z),
+                (x + 1, z),
+                (x, z.wrapping_sub(1)),
+                (x, z + 1),
+            ];
+            for (x, z) in neighbors {
+                if x >= grid.width() || z >= grid.height() {
+                    continue;
+                }
+                let index = grid.index(x, z);
+                if !seen[index] && walkable(grid.get(x, z)) {
+                    seen[index] = true;
+                    stack.push((x, z));
+                }
+            }
+        }
+        regions.push(Region {
+            start: (x, z),
+            size,
+        });
+    }
+
+    regions
+}
+
+/// Check everything we can without a navmesh.
+pub fn check_grid(grid: &LevelGrid, settings: &NavMeshSettings) -> Vec<Problem> {
+    let mut problems = Vec::new();
+
+    let mut unknown: Vec<([u8; 3], (u32, u32), usize)> = Vec::new();
+    for (x, z, cell) in grid.cells() {
+        if let Cell::Unknown(color) = cell {
+            match unknown.iter_mut().find(|(c, _, _)| *c == color) {
+                Some((_, _, count)) => *count += 1,
+                None => unknown.push((color, (x, z), 1)),
+            }
+        }
+    }
+    for (color, (x, z), count) in unknown {
+        problems.push(Problem::error(format!(
+            "unknown color {color:?} in {count} cell(s), first at ({x}, {z})"
+        )));
+    }
+
+    // Compare in whole cells, as the extents are multiples of `PIXEL` that
+    // don't survive the round trip through floats exactly.
+    let covered = (settings.world_half_extents * 2.0 / LevelGrid::PIXEL).round() as u32;
+    if grid.width() > covered || grid.height() > covered {
+        problems.push(Problem::error(format!(
+            "level is {} x {} cells, but the navmesh only covers {covered} x {covered}",
+            grid.width(),
+            grid.height()
+        )));
+    }
+
+    let props = grid.props();
+    if props.player_spawns.is_empty() {
+        problems.push(Problem::warning(
+            "no player spawns; players will spawn in the middle of the level".into(),
+        ));
+    }
+
+    for (x, z, cell) in grid.cells() {
+        if let Cell::Platform(channel) = cell {
+            if !grid
+                .cells()
+                .any(|(_, _, c)| c == Cell::PlatformEnd(channel))
+            {
+                problems.push(Problem::error(format!(
+                    "platform at ({x}, {z}) on channel {channel} has no end"
+                )));
+            }
+        }
+    }
+
+    problems
+}
+
+/// Check that every spawn point, and every floor region, can be reached from
+/// the first player spawn.
+pub fn check_nav_mesh(
+    grid: &LevelGrid,
+    tiles: &NavMeshTiles,
+    settings: &NavMeshSettings,
+) -> Vec<Problem> {
+    if tiles.get_tiles().is_empty() {
+        return vec![Problem::error("the navmesh is empty".into())];
+    }
+
+    let regions = regions(grid);
+    let props = grid.props();
+    let Some(origin) = props.player_spawns.first().copied().or_else(|| {
+        let largest = regions.iter().max_by_key(|region| region.size)?;
+        Some(grid.cell_center(largest.start.0, largest.start.1))
+    }) else {
+        return vec![Problem::error("the level has no floor".into())];
+    };
+
+    let reachable = |end: Vec3| {
+        find_path(
+            tiles,
+            settings,
+            origin,
+            end,
+            Some(SEARCH_RADIUS),
+            Some(&[1.0, 0.5]),
+        )
+        .is_ok()
+    };
+
+    let mut problems = Vec::new();
+    let spawns = props
+        .player_spawns
+        .iter()
+        .map(|loc| ("player", loc))
+        .chain(props.enemy_spawns.iter().map(|loc| ("enemy", loc)));
+    for (kind, &loc) in spawns {
+        if loc != origin && !reachable(loc) {
+            let (x, z) = grid.cell_at(loc).unwrap_or_default();
+            problems.push(Problem::error(format!(
+                "{kind} spawn at ({x}, {z}) can't be reached"
+            )));
+        }
+    }
+
+    for region in regions {
+        let (x, z) = region.start;
+        if !reachable(grid.cell_center(x, z)) {
+            problems.push(Problem::warning(format!(
+                "floor region of {} cell(s) starting at ({x}, {z}) can't be reached",
+                region.size
+            )));
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod test {
+    use super::check_grid;
+    use super::regions;
+    use super::Severity;
+    use crate::ai::pathfind::nav_mesh_settings;
+    use crate::level::Cell;
+    use crate::level::LevelGrid;
+    use crate::level::LevelProps;
+    use crate::level::TEST_LEVEL;
+
+    #[test]
+    fn finds_regions() {
+        let mut grid = LevelGrid::new(5, 3, Cell::Floor);
+        for z in 0..3 {
+            grid.set(2, z, Cell::Wall);
+        }
+        grid.set(4, 2, Cell::Pit);
+
+        let regions = regions(&grid);
+        assert_eq!(regions.len(), 2);
+        assert_eq!(regions[0].start, (0, 0));
+        assert_eq!(regions[0].size, 6);
+        assert_eq!(regions[1].start, (3, 0));
+        assert_eq!(regions[1].size, 5);
+    }
+
+    #[test]
+    fn finds_unknown_colors() {
+        let mut grid = LevelGrid::new(3, 3, Cell::PlayerSpawn);
+        grid.set(1, 1, Cell::Unknown([1, 2, 3]));
+        grid.set(2, 2, Cell::Unknown([1, 2, 3]));
+
+        let problems = check_grid(&grid, &nav_mesh_settings(&LevelProps::default()));
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].severity, Severity::Error);
+        assert!(problems[0].message.contains("2 cell(s)"));
+    }
+
+    #[test]
+    fn test_level_fits() {
+        let path = format!("{}/../../{TEST_LEVEL}", env!("CARGO_MANIFEST_DIR"));
+        let grid = LevelGrid::load(path).unwrap();
+
+        let problems = check_grid(&grid, &nav_mesh_settings(&LevelProps::default()));
+        let errors: Vec<_> = problems
+            .iter()
+            .filter(|problem| problem.severity == Severity::Error)
+            .collect();
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+}
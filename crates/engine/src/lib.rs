@@ -12,6 +12,8 @@ use bevy_app::PostUpdate;
 use bevy_app::Startup;
 use bevy_ecs::bundle::Bundle;
 use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::event::Event;
 use bevy_ecs::schedule::IntoSystemConfigs;
 use bevy_ecs::schedule::IntoSystemSetConfigs;
 use bevy_ecs::schedule::SystemSet;
@@ -42,15 +44,21 @@ use bevy_transform::components::GlobalTransform;
 use bevy_transform::components::Transform;
 use collision::TrackCollisionBundle;
 use input::pause_resume;
+use level::mechanism::Channels;
 use level::InLevel;
 use level::LevelProps;
 use movement::DesiredMove;
 use movement::MaxSpeed;
+use multiplayer::Buttons;
 use multiplayer::PlayerInputs;
 use physics::PhysicsPlugin;
+use run::Run;
 use status_effect::charge::charge_tick;
+use status_effect::charge::discharge_system;
+use status_effect::charge::force_system;
 use status_effect::phased::phased_tick;
 use status_effect::temperature::temperature_tick;
+use status_effect::time_dilation::dilate_velocity;
 use status_effect::time_dilation::time_dilation_tick;
 use status_effect::StatusBundle;
 use status_effect::TimeDilation;
@@ -69,6 +77,7 @@ pub mod movement;
 pub mod multiplayer;
 pub mod physics;
 pub mod player;
+pub mod run;
 pub mod status_effect;
 pub mod time;
 
@@ -81,6 +90,8 @@ pub enum AppState {
     #[default]
     Running,
     Menu,
+    /// Editing a level in the client.
+    Editor,
 }
 pub const SCHEDULE: FixedUpdate = FixedUpdate;
 
@@ -99,6 +110,10 @@ pub const CONTACT_SKIN: ContactSkin = ContactSkin(0.01);
 pub struct Health {
     pub cur: f32,
     pub max: f32,
+    /// Temporary health, which absorbs damage before `cur`.
+    pub shield: f32,
+    /// All the damage done to us through `take`, ever.
+    pub taken: f32,
     // This prevents death, ticking every frame below 0 heath. It was added to
     // have some abilities spawn things that can't die.
     pub death_delay: Dur,
@@ -113,15 +128,30 @@ impl Health {
         Self {
             cur: max,
             max,
+            shield: 0.0,
+            taken: 0.0,
             death_delay,
         }
     }
 
-    pub fn take(&mut self, dmg: f32, time_dilation: &TimeDilation) {
+    /// Returns how much damage was actually done, counting what the shield
+    /// absorbed.
+    pub fn take(&mut self, dmg: f32, time_dilation: &TimeDilation) -> f32 {
         // Note: Damage can be negative (for healing) so we need to clamp by
         // both min (0) and max.
-        let damage = dmg * time_dilation.factor();
+        let mut damage = dmg * time_dilation.factor();
+        let mut done = 0.0;
+        if damage > 0.0 {
+            let absorbed = damage.min(self.shield);
+            self.shield -= absorbed;
+            damage -= absorbed;
+            done += absorbed;
+        }
+        let before = self.cur;
         self.cur = (self.cur - damage).clamp(0.0, self.max);
+        done += (before - self.cur).max(0.0);
+        self.taken += done;
+        done
     }
 
     pub fn die(&mut self) {
@@ -129,6 +159,17 @@ impl Health {
     }
 }
 
+/// Damage that `source` dealt to `target`.
+///
+/// Note: Only direct hits and explosions send these; things like burning and
+/// discharge don't.
+#[derive(Event, Debug, Copy, Clone)]
+pub struct DamageEvent {
+    pub source: Entity,
+    pub target: Entity,
+    pub amount: f32,
+}
+
 #[derive(Component, Default, Debug, Reflect)]
 pub struct Energy {
     pub cur: f32,
@@ -287,6 +328,7 @@ struct Character {
     desired_movement: DesiredMove,
     ability_offset: AbilityOffset,
     marker: CharacterMarker,
+    buttons: Buttons,
 }
 
 #[derive(Resource, Reflect, Default, Debug)]
@@ -326,7 +368,10 @@ impl Plugin for GamPlugin {
             })
             .insert_resource(PlayerInputs::default())
             .insert_resource(LevelProps::default())
-            .init_resource::<AbilityMap>();
+            .init_resource::<Run>()
+            .init_resource::<Channels>()
+            .init_resource::<AbilityMap>()
+            .add_event::<DamageEvent>();
 
         let physics = PhysicsPlugin::new();
 
@@ -350,12 +395,12 @@ impl Plugin for GamPlugin {
         );
 
         // Systems in order
-        app.add_systems(Startup, level::test_level).add_systems(
+        app.add_systems(Startup, run::start_run).add_systems(
             SCHEDULE,
             (
                 time::frame_counter.in_set(GameSet::Timer),
                 (
-                    time_dilation_tick,
+                    (time_dilation_tick, dilate_velocity).chain(),
                     temperature_tick,
                     charge_tick,
                     phased_tick,
@@ -367,14 +412,24 @@ impl Plugin for GamPlugin {
                     .in_set(GameSet::Reset),
                 input::apply_inputs.in_set(GameSet::Input),
                 ai::systems().in_set(GameSet::Ai),
-                (ability::bullet::collision_system,)
+                (ability::bullet::collision_system, discharge_system)
                     .chain()
                     .in_set(GameSet::Collision),
                 (
                     // Misc; categorize futher?
                     movement::apply_movement,
+                    movement::knockback_system,
+                    force_system,
                     // death_callback::explosion_grow_system,
                     lifecycle::fall,
+                    (
+                        level::mechanism::switch_system,
+                        level::mechanism::channel_system,
+                        level::mechanism::move_system,
+                        level::mechanism::carry_system,
+                    )
+                        .chain()
+                        .before(lifecycle::fall),
                 )
                     .in_set(GameSet::Stuff),
                 (
@@ -385,7 +440,10 @@ impl Plugin for GamPlugin {
                 (
                     lifecycle::lifetime_system,
                     lifecycle::die,
+                    run::store_carry_over,
+                    run::advance_stage,
                     lifecycle::reset,
+                    run::apply_carry_over,
                     time::debug_frame_system,
                     // Entities spawn with 0 mass, so we need to place this
                     // after we run physics, after firing the bullet.
@@ -2,6 +2,7 @@ use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::query::QueryData;
 use bevy_ecs::query::With;
+use bevy_ecs::query::Without;
 use bevy_ecs::system::Commands;
 use bevy_ecs::system::In;
 use bevy_ecs::system::Query;
@@ -21,14 +22,19 @@ use bevy_rapier3d::prelude::Velocity;
 use bevy_transform::components::Transform;
 
 use crate::ability::cooldown::Cooldown;
+use crate::ability::turret::Turret;
 use crate::ability::AbilityMap;
 use crate::ai::charge::ChargeAi;
 use crate::ai::AiBundle;
 use crate::collision::TrackCollisionBundle;
 use crate::level::InLevel;
 use crate::level::LevelProps;
+use crate::multiplayer::Buttons;
 use crate::player::character_collider;
 use crate::player::PlayerInfo;
+use crate::run::Run;
+use crate::run::RunState;
+use crate::run::StageGoal;
 use crate::status_effect::StatusProps;
 use crate::status_effect::TimeDilation;
 use crate::time::Dur;
@@ -114,16 +120,19 @@ pub fn die(mut commands: Commands, mut query: Query<DieQuery>, tick_counter: Res
 }
 
 pub const ENERGY_REGEN: f32 = 0.5;
+const ENEMY_HEALTH: f32 = 10.0;
+const BOSS_HEALTH: f32 = 250.0;
 
 fn spawn_enemies(
     commands: &mut Commands,
     num: usize,
+    health: f32,
     level: &LevelProps,
     rapier_context: &RapierContext,
     ability_map: &AbilityMap,
 ) {
     for _ in 0..num {
-        let loc = level.point_in_plane(rapier_context);
+        let loc = level.enemy_spawn(rapier_context);
         let ai_bundle = AiBundle::<ChargeAi>::default();
         let ability_ids = ai_bundle.ai.ability_ids.clone();
 
@@ -132,7 +141,7 @@ fn spawn_enemies(
                 Enemy,
                 ai_bundle,
                 Character {
-                    health: Health::new(10.0),
+                    health: Health::new(health),
                     energy: Energy::new(50.0, 0.2),
                     object: Object {
                         transform: Transform::from_translation(
@@ -151,7 +160,7 @@ fn spawn_enemies(
                             capacitance: 1.0,
                         }
                         .into(),
-                        collisions: TrackCollisionBundle::off(),
+                        collisions: TrackCollisionBundle::on(),
                     },
                     max_speed: Default::default(),
                     friction: Friction {
@@ -163,6 +172,7 @@ fn spawn_enemies(
                     desired_movement: Default::default(),
                     ability_offset: ((-PLAYER_HEIGHT * 0.5) + ABILITY_Y.y).into(),
                     marker: CharacterMarker,
+                    buttons: Buttons::default(),
                     contact_skin: CONTACT_SKIN,
                 },
             ))
@@ -206,7 +216,7 @@ fn spawn_allies(
                             capacitance: 1.0,
                         }
                         .into(),
-                        collisions: TrackCollisionBundle::off(),
+                        collisions: TrackCollisionBundle::on(),
                     },
                     contact_skin: CONTACT_SKIN,
                     health: Health::new(100.0),
@@ -221,6 +231,7 @@ fn spawn_allies(
                     desired_movement: Default::default(),
                     ability_offset: ((-PLAYER_HEIGHT * 0.5) + ABILITY_Y.y).into(),
                     marker: CharacterMarker,
+                    buttons: Buttons::default(),
                 },
             ))
             .id();
@@ -232,35 +243,77 @@ fn spawn_allies(
 
 pub fn reset(
     mut commands: Commands,
-    enemy_query: Query<Entity, With<Enemy>>,
-    ally_query: Query<Entity, With<Ally>>,
+    // Turrets don't count; they're temporary.
+    enemy_query: Query<Entity, (With<Enemy>, Without<Turret>)>,
+    ally_query: Query<Entity, (With<Ally>, Without<Turret>)>,
     mut player_query: Query<(Entity, &mut Health, &mut Energy), With<Player>>,
     player_info_query: Query<&PlayerInfo>,
     mut num_ai: ResMut<NumAi>,
+    mut run: ResMut<Run>,
+    counter: Res<FrameCounter>,
     level: Res<LevelProps>,
     rapier_context: ReadDefaultRapierContext,
     ability_map: Res<AbilityMap>,
 ) {
-    if enemy_query.iter().next().is_none() {
-        num_ai.enemies += 1;
-        spawn_enemies(
-            &mut commands,
-            num_ai.enemies,
-            &level,
-            &rapier_context,
-            &ability_map,
-        );
+    match run.state() {
+        // There's either no level, or it's not ready yet.
+        RunState::Starting | RunState::Won => return,
+        RunState::Playing | RunState::Transitioning(_) => (),
+    }
+    let goal = run.stage().goal;
+
+    if run.is_playing() && enemy_query.iter().next().is_none() {
+        match goal {
+            StageGoal::Endless => {
+                num_ai.enemies += 1;
+                spawn_enemies(
+                    &mut commands,
+                    num_ai.enemies,
+                    ENEMY_HEALTH,
+                    &level,
+                    &rapier_context,
+                    &ability_map,
+                );
 
-        for (_entity, mut health, mut energy) in &mut player_query {
-            health.cur = health.max;
-            energy.cur = energy.max;
+                for (_entity, mut health, mut energy) in &mut player_query {
+                    health.cur = health.max;
+                    energy.cur = energy.max;
+                }
+            }
+            StageGoal::Waves(waves) if num_ai.enemies < waves => {
+                num_ai.enemies += 1;
+                spawn_enemies(
+                    &mut commands,
+                    num_ai.enemies,
+                    ENEMY_HEALTH,
+                    &level,
+                    &rapier_context,
+                    &ability_map,
+                );
+            }
+            StageGoal::Boss if num_ai.enemies == 0 => {
+                num_ai.enemies += 1;
+                spawn_enemies(
+                    &mut commands,
+                    1,
+                    BOSS_HEALTH,
+                    &level,
+                    &rapier_context,
+                    &ability_map,
+                );
+            }
+            StageGoal::Waves(_) | StageGoal::Boss => run.clear_stage(&counter),
         }
     }
 
     if player_query.iter().next().is_none() {
-        num_ai.enemies = num_ai.enemies.saturating_sub(1);
-        for info in player_info_query.iter() {
-            info.spawn_player(&mut commands, &ability_map);
+        // In endless mode, dying costs you a wave.
+        if goal == StageGoal::Endless {
+            num_ai.enemies = num_ai.enemies.saturating_sub(1);
+        }
+        for (i, info) in player_info_query.iter().enumerate() {
+            let loc = level.player_spawn(i);
+            info.spawn_player(&mut commands, &ability_map, run.loadout(info), loc);
         }
     }
 
@@ -1,16 +1,26 @@
 use std::ops::MulAssign;
 
 use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::Without;
+use bevy_ecs::system::Commands;
 use bevy_ecs::system::Query;
 use bevy_math::Vec2;
+use bevy_math::Vec3;
+use bevy_rapier3d::prelude::ExternalForce;
 use bevy_rapier3d::prelude::Velocity;
 use bevy_reflect::Reflect;
+use serde::Deserialize;
+use serde::Serialize;
 
 use crate::status_effect::TimeDilation;
+use crate::time::Dur;
 use crate::time::FREQUENCY;
 use crate::time::TIMESTEP;
+use crate::Libm;
 use crate::To2d;
 use crate::To3d;
+use crate::PLAYER_MASS;
 
 /// The desired movement of an entity.
 ///
@@ -31,7 +41,7 @@ impl DesiredMove {
 
 /// We currently move Characters by applying an impulse; this is the highest
 /// impulse they can use.
-#[derive(Component, Copy, Clone, Debug, Reflect)]
+#[derive(Component, Copy, Clone, Debug, Reflect, Serialize, Deserialize)]
 pub struct MaxSpeed {
     pub accel: f32,
     pub speed: f32,
@@ -53,7 +63,9 @@ impl Default for MaxSpeed {
     }
 }
 
-pub fn apply_movement(mut query: Query<(&DesiredMove, &mut Velocity, &MaxSpeed, &TimeDilation)>) {
+pub fn apply_movement(
+    mut query: Query<(&DesiredMove, &mut Velocity, &MaxSpeed, &TimeDilation), Without<Knockback>>,
+) {
     for (desired, mut velocity, max_speed, time_dilation) in &mut query {
         let factor = time_dilation.factor();
         let desired_v = max_speed.speed * desired.dir * factor;
@@ -64,3 +76,33 @@ pub fn apply_movement(mut query: Query<(&DesiredMove, &mut Velocity, &MaxSpeed,
         velocity.linvel += (delta_a * TIMESTEP).to_3d(0.0);
     }
 }
+
+/// Something being shoved around. It can't move under its own power until this
+/// wears off, so a shove near a ledge can send it over.
+#[derive(Component, Debug)]
+pub struct Knockback {
+    pub force: Vec3,
+    pub remaining: Dur,
+}
+
+impl Knockback {
+    /// The force to push something of the given mass with, so that a
+    /// `PLAYER_MASS` target gets the given acceleration.
+    ///
+    /// Heavier things get pushed around less, but not proportionally less.
+    pub fn force(accel: f32, mass: f32) -> f32 {
+        accel * Libm::sqrt(mass * PLAYER_MASS)
+    }
+}
+
+pub fn knockback_system(
+    mut commands: Commands,
+    mut query: Query<(Entity, &mut Knockback, &mut ExternalForce, &TimeDilation)>,
+) {
+    for (entity, mut knockback, mut force, dilation) in &mut query {
+        force.force += knockback.force * dilation.factor();
+        if knockback.remaining.tick(dilation) {
+            commands.entity(entity).remove::<Knockback>();
+        }
+    }
+}
@@ -1,5 +1,6 @@
 use core::fmt;
 
+use bevy_ecs::component::Component;
 use bevy_ecs::entity::Entity;
 use bevy_ecs::system::Commands;
 use bevy_ecs::system::Resource;
@@ -77,6 +78,84 @@ impl Action {
     }
 }
 
+/// The state of a single button, from one frame to the next.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ButtonState {
+    Up,
+    /// Pressed this frame.
+    Pressed,
+    /// Held for this many frames, including the one it was pressed.
+    Held(u32),
+    /// Released this frame, after being held for this many frames.
+    Released(u32),
+}
+
+/// The buttons a character is using, along with how long they've been held.
+///
+/// This is derived entirely from consecutive inputs, so it's deterministic.
+#[derive(Component, Default, Debug, Clone)]
+pub struct Buttons {
+    current: Action,
+    previous: Action,
+    /// How many frames each button has been held, indexed by its bit.
+    held: [u32; 16],
+}
+
+impl Buttons {
+    /// Call once per frame, with the buttons that are currently down.
+    pub fn update(&mut self, current: Action) {
+        self.previous = self.current;
+        self.current = current;
+        for (i, held) in self.held.iter_mut().enumerate() {
+            let action = Action::from(1 << i);
+            *held = match (self.previous.contains(action), current.contains(action)) {
+                (false, false) => 0,
+                (false, true) => 1,
+                (true, true) => *held + 1,
+                // Keep the count for one frame, so we know how long it was held.
+                (true, false) => *held,
+            };
+        }
+    }
+
+    pub fn state(&self, action: Action) -> ButtonState {
+        let held = self.held[action.bits().trailing_zeros() as usize];
+        match (
+            self.previous.contains(action),
+            self.current.contains(action),
+        ) {
+            (false, false) => ButtonState::Up,
+            (false, true) => ButtonState::Pressed,
+            (true, true) => ButtonState::Held(held),
+            (true, false) => ButtonState::Released(held),
+        }
+    }
+
+    pub fn just_pressed(&self, action: Action) -> bool {
+        self.state(action) == ButtonState::Pressed
+    }
+
+    pub fn just_released(&self, action: Action) -> bool {
+        matches!(self.state(action), ButtonState::Released(_))
+    }
+
+    /// How many frames this button has been held, or 0 if it's up.
+    pub fn held_for(&self, action: Action) -> u32 {
+        if self.current.contains(action) {
+            self.held[action.bits().trailing_zeros() as usize]
+        } else {
+            0
+        }
+    }
+
+    /// Fire every ability whose button is down, or was just released.
+    ///
+    /// Note: It's up to the ability to decide which of those it cares about.
+    pub fn fire_abilities(&self, commands: &mut Commands, user: Entity, abilities: &Abilities) {
+        (self.current | self.previous).fire_abilities(commands, user, abilities);
+    }
+}
+
 impl Serialize for Action {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -167,7 +246,10 @@ impl Input {
 
 #[cfg(test)]
 mod test {
+    use super::Action;
     use super::BoundedF8;
+    use super::ButtonState;
+    use super::Buttons;
 
     #[test]
     fn movement_conversion() {
@@ -177,4 +259,23 @@ mod test {
             assert_eq!(BoundedF8::from(expected), bounded);
         }
     }
+
+    #[test]
+    fn button_states() {
+        let mut buttons = Buttons::default();
+        let states = [
+            (Action::LeftArm, ButtonState::Pressed),
+            (Action::LeftArm, ButtonState::Held(2)),
+            (Action::LeftArm | Action::Legs, ButtonState::Held(3)),
+            (Action::Legs, ButtonState::Released(3)),
+            (Action::none(), ButtonState::Up),
+            (Action::LeftArm, ButtonState::Pressed),
+        ];
+        for (input, expected) in states {
+            buttons.update(input);
+            assert_eq!(buttons.state(Action::LeftArm), expected);
+        }
+        assert_eq!(buttons.held_for(Action::LeftArm), 1);
+        assert_eq!(buttons.state(Action::Legs), ButtonState::Up);
+    }
 }
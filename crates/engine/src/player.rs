@@ -16,13 +16,15 @@ use serde::Deserialize;
 use serde::Serialize;
 
 use crate::ability::cooldown::Cooldown;
-use crate::ability::AbilityId;
+use crate::ability::tree::AbilityChoice;
+use crate::ability::Ability;
 use crate::ability::AbilityMap;
 use crate::ability::NonArmSlot;
 use crate::ability::SideEnum;
 use crate::collision::TrackCollisionBundle;
 use crate::level::InLevel;
 use crate::lifecycle::ENERGY_REGEN;
+use crate::multiplayer::Buttons;
 use crate::status_effect::StatusProps;
 use crate::Ally;
 use crate::Character;
@@ -40,33 +42,45 @@ use crate::PLAYER_HEIGHT;
 use crate::PLAYER_MASS;
 use crate::PLAYER_R;
 
+/// A loadout: which ability goes in each slot, with its skill tree picks.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AbilityIds {
-    pub left_arm: AbilityId,
-    pub right_arm: AbilityId,
-    pub left_shoulder: AbilityId,
-    pub right_shoulder: AbilityId,
-    pub legs: AbilityId,
-    pub head: AbilityId,
+    pub left_arm: AbilityChoice,
+    pub right_arm: AbilityChoice,
+    pub left_shoulder: AbilityChoice,
+    pub right_shoulder: AbilityChoice,
+    pub legs: AbilityChoice,
+    pub head: AbilityChoice,
+    /// A passive, which has no button.
+    #[serde(default)]
+    pub body: AbilityChoice,
 }
 
 impl AbilityIds {
     pub fn build(&self, map: &AbilityMap, commands: &mut Commands, entity: Entity) -> Abilities {
-        let left_arm = map.get_arm(SideEnum::Left, &self.left_arm);
-        let right_arm = map.get_arm(SideEnum::Right, &self.right_arm);
-        let left_shoulder = map.get(NonArmSlot::Shoulder(SideEnum::Left), &self.left_shoulder);
-        let right_shoulder = map.get(NonArmSlot::Shoulder(SideEnum::Right), &self.right_shoulder);
-        let legs = map.get(NonArmSlot::Legs, &self.legs);
-        let head = map.get(NonArmSlot::Head, &self.head);
+        let left_arm = map.get_arm(SideEnum::Left, &self.left_arm.id);
+        let right_arm = map.get_arm(SideEnum::Right, &self.right_arm.id);
+        let left_shoulder = map.get(NonArmSlot::Shoulder(SideEnum::Left), &self.left_shoulder.id);
+        let right_shoulder = map.get(
+            NonArmSlot::Shoulder(SideEnum::Right),
+            &self.right_shoulder.id,
+        );
+        let legs = map.get(NonArmSlot::Legs, &self.legs.id);
+        let head = map.get(NonArmSlot::Head, &self.head.id);
+        let body = map.get(NonArmSlot::Body, &self.body.id);
 
-        commands.run_system_with_input(left_arm.0.setup, entity);
-        commands.run_system_with_input(left_arm.1.setup, entity);
-        commands.run_system_with_input(right_arm.0.setup, entity);
-        commands.run_system_with_input(right_arm.1.setup, entity);
-        commands.run_system_with_input(left_shoulder.setup, entity);
-        commands.run_system_with_input(right_shoulder.setup, entity);
-        commands.run_system_with_input(legs.setup, entity);
-        commands.run_system_with_input(head.setup, entity);
+        let mut setup = |ability: &Ability, choice: &AbilityChoice| {
+            commands.run_system_with_input(ability.setup, (entity, choice.nodes.clone()));
+        };
+        setup(left_arm.0, &self.left_arm);
+        setup(left_arm.1, &self.left_arm);
+        setup(right_arm.0, &self.right_arm);
+        setup(right_arm.1, &self.right_arm);
+        setup(left_shoulder, &self.left_shoulder);
+        setup(right_shoulder, &self.right_shoulder);
+        setup(legs, &self.legs);
+        setup(head, &self.head);
+        setup(body, &self.body);
 
         Abilities {
             left_arm: left_arm.0.fire,
@@ -100,7 +114,13 @@ pub struct PlayerInfo {
 }
 
 impl PlayerInfo {
-    pub fn spawn_player(&self, commands: &mut Commands, ability_map: &AbilityMap) {
+    pub fn spawn_player(
+        &self,
+        commands: &mut Commands,
+        ability_map: &AbilityMap,
+        ability_ids: &AbilityIds,
+        loc: Vec3,
+    ) {
         let id = commands
             .spawn((
                 Target::default(),
@@ -112,7 +132,9 @@ impl PlayerInfo {
                         foot_offset: (-PLAYER_HEIGHT * 0.5).into(),
                         body: RigidBody::Dynamic,
                         locked_axes: LockedAxes::ROTATION_LOCKED,
-                        transform: Transform::from_xyz(0.0, PLAYER_HEIGHT * 0.5, 0.0),
+                        transform: Transform::from_translation(
+                            loc + Vec3::new(0.0, PLAYER_HEIGHT * 0.5, 0.0),
+                        ),
                         mass: MassBundle::new(PLAYER_MASS),
                         velocity: Velocity::zero(),
                         force: ExternalForce::default(),
@@ -122,7 +144,9 @@ impl PlayerInfo {
                             capacitance: 1.0,
                         }
                         .into(),
-                        collisions: TrackCollisionBundle::off(),
+                        // Characters track collisions, so touching ones can
+                        // discharge.
+                        collisions: TrackCollisionBundle::on(),
                     },
                     contact_skin: CONTACT_SKIN,
                     health: Health::new(100.0),
@@ -137,10 +161,11 @@ impl PlayerInfo {
                     desired_movement: Default::default(),
                     ability_offset: ((-PLAYER_HEIGHT * 0.5) + ABILITY_Y.y).into(),
                     marker: CharacterMarker,
+                    buttons: Buttons::default(),
                 },
             ))
             .id();
-        let abilities = self.ability_ids.build(ability_map, commands, id);
+        let abilities = ability_ids.build(ability_map, commands, id);
         commands.entity(id).insert(abilities);
         tracing::debug!(?id, "Spawning player");
     }
@@ -0,0 +1,256 @@
+use bevy_ecs::entity::Entity;
+use bevy_ecs::query::Added;
+use bevy_ecs::query::With;
+use bevy_ecs::system::Commands;
+use bevy_ecs::system::Query;
+use bevy_ecs::system::Res;
+use bevy_ecs::system::ResMut;
+use bevy_ecs::system::Resource;
+use bevy_state::state::NextState;
+use bevy_utils::HashMap;
+
+use crate::level::despawn_level;
+use crate::level::InLevel;
+use crate::level::LevelProps;
+use crate::level::LevelSource;
+use crate::level::TEST_LEVEL;
+use crate::player::AbilityIds;
+use crate::player::PlayerInfo;
+use crate::time::Dur;
+use crate::time::Frame;
+use crate::time::FrameCounter;
+use crate::AppState;
+use crate::Energy;
+use crate::Health;
+use crate::NumAi;
+use crate::Player;
+
+/// How many frames to wait between clearing a stage and loading the next one.
+const TRANSITION_FRAMES: u32 = 128;
+
+/// What a stage needs for it to be cleared.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum StageGoal {
+    /// Waves keep getting bigger; this stage never ends.
+    Endless,
+    /// Clear this many waves of enemies.
+    Waves(usize),
+    /// Defeat a single, much tougher, enemy.
+    Boss,
+}
+
+#[derive(Debug, Clone)]
+pub struct Stage {
+    pub level: LevelSource,
+    pub goal: StageGoal,
+}
+
+/// Where we are in a run.
+///
+/// Note: This is deliberately not a bevy `State`, as state transitions happen
+/// outside of our fixed schedule, and we need every client to move between
+/// stages on the same `Frame`. Instead, it sits under `AppState::Running`: the
+/// run only advances while the game is running, and winning it goes to the
+/// menu.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum RunState {
+    /// The level has been spawned, but physics hasn't seen it yet.
+    #[default]
+    Starting,
+    Playing,
+    /// The stage is clear, and we'll load the next one on this frame.
+    Transitioning(Frame),
+    /// Every stage has been cleared.
+    Won,
+}
+
+/// What a living player keeps when moving to the next stage.
+#[derive(Debug, Copy, Clone)]
+struct CarryOver {
+    health: f32,
+    energy: f32,
+}
+
+/// A run is a sequence of stages, each played in its own level.
+#[derive(Resource, Debug)]
+pub struct Run {
+    stages: Vec<Stage>,
+    stage: usize,
+    state: RunState,
+    carry_over: HashMap<Player, CarryOver>,
+    /// Each player's abilities and skill tree picks, as of the last stage they
+    /// cleared.
+    loadouts: HashMap<Player, AbilityIds>,
+}
+
+impl Default for Run {
+    /// A single, endless stage in the test level.
+    fn default() -> Self {
+        Self::new(vec![Stage {
+            level: LevelSource::Image(TEST_LEVEL.into()),
+            goal: StageGoal::Endless,
+        }])
+    }
+}
+
+impl Run {
+    pub fn new(stages: Vec<Stage>) -> Self {
+        assert!(!stages.is_empty(), "A run needs at least one stage");
+        Self {
+            stages,
+            stage: 0,
+            state: RunState::default(),
+            carry_over: HashMap::default(),
+            loadouts: HashMap::default(),
+        }
+    }
+
+    /// A short run through a hand-made level, a generated one, and a boss.
+    pub fn standard(seed: u64) -> Self {
+        Self::new(vec![
+            Stage {
+                level: LevelSource::Image(TEST_LEVEL.into()),
+                goal: StageGoal::Waves(3),
+            },
+            Stage {
+                level: LevelSource::Generated {
+                    seed,
+                    width: 50,
+                    height: 50,
+                },
+                goal: StageGoal::Waves(4),
+            },
+            Stage {
+                level: LevelSource::Generated {
+                    seed: seed.wrapping_add(1),
+                    width: 40,
+                    height: 40,
+                },
+                goal: StageGoal::Boss,
+            },
+        ])
+    }
+
+    pub fn stage(&self) -> &Stage {
+        &self.stages[self.stage]
+    }
+
+    /// The index of the current stage, starting from 0.
+    pub fn stage_index(&self) -> usize {
+        self.stage
+    }
+
+    pub fn num_stages(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn state(&self) -> RunState {
+        self.state
+    }
+
+    pub fn is_playing(&self) -> bool {
+        self.state == RunState::Playing
+    }
+
+    /// The loadout to spawn this player with.
+    pub fn loadout<'a>(&'a self, info: &'a PlayerInfo) -> &'a AbilityIds {
+        self.loadouts.get(&info.handle).unwrap_or(&info.ability_ids)
+    }
+
+    /// Whether we're moving to the next stage on this frame.
+    fn leaving_stage(&self, counter: &FrameCounter) -> bool {
+        let due = matches!(self.state, RunState::Transitioning(frame) if frame.before_now(counter));
+        due && self.stage + 1 < self.stages.len()
+    }
+
+    /// Mark the current stage as cleared, scheduling the transition to the
+    /// next one.
+    pub fn clear_stage(&mut self, counter: &FrameCounter) {
+        if self.state == RunState::Playing {
+            self.state = RunState::Transitioning(counter.at(Dur::new(TRANSITION_FRAMES)));
+        }
+    }
+}
+
+/// Load the first stage of the run.
+pub fn start_run(mut commands: Commands, run: Res<Run>, mut props: ResMut<LevelProps>) {
+    run.stage().level.load().spawn(&mut commands, &mut props);
+}
+
+/// Remember what players have as they leave a stage.
+pub fn store_carry_over(
+    mut run: ResMut<Run>,
+    counter: Res<FrameCounter>,
+    player_q: Query<(&Player, &Health, &Energy)>,
+    info_q: Query<&PlayerInfo>,
+) {
+    if !run.leaving_stage(&counter) {
+        return;
+    }
+
+    run.carry_over = player_q
+        .iter()
+        .map(|(player, health, energy)| {
+            let carry_over = CarryOver {
+                health: health.cur,
+                energy: energy.cur,
+            };
+            (*player, carry_over)
+        })
+        .collect();
+    let loadouts = info_q
+        .iter()
+        .map(|info| (info.handle, run.loadout(info).clone()))
+        .collect();
+    run.loadouts = loadouts;
+}
+
+/// Move between stages.
+///
+/// Note: This needs to run after physics, so the level is in the physics world
+/// by the time we start playing.
+pub fn advance_stage(
+    mut commands: Commands,
+    mut run: ResMut<Run>,
+    counter: Res<FrameCounter>,
+    mut num_ai: ResMut<NumAi>,
+    mut props: ResMut<LevelProps>,
+    level_q: Query<Entity, With<InLevel>>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    let state = run.state;
+    match state {
+        RunState::Starting => run.state = RunState::Playing,
+        RunState::Transitioning(frame) if frame.before_now(&counter) => {
+            if run.stage + 1 == run.stages.len() {
+                tracing::info!(frame = ?counter.frame, "Run won");
+                run.state = RunState::Won;
+                next_state.set(AppState::Menu);
+                return;
+            }
+
+            despawn_level(&mut commands, &level_q);
+            num_ai.enemies = 0;
+
+            run.stage += 1;
+            run.state = RunState::Starting;
+            tracing::info!(frame = ?counter.frame, stage = run.stage, "Loading stage");
+            run.stage().level.load().spawn(&mut commands, &mut props);
+        }
+        RunState::Transitioning(_) | RunState::Playing | RunState::Won => (),
+    }
+}
+
+/// Give players back what they had at the end of the last stage.
+pub fn apply_carry_over(
+    mut run: ResMut<Run>,
+    mut query: Query<(&Player, &mut Health, &mut Energy), Added<Player>>,
+) {
+    for (player, mut health, mut energy) in &mut query {
+        if let Some(carry_over) = run.carry_over.remove(player) {
+            // Don't carry a dead player into the next stage.
+            health.cur = carry_over.health.clamp(1.0, health.max);
+            energy.cur = carry_over.energy.min(energy.max);
+        }
+    }
+}
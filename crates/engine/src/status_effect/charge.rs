@@ -1,16 +1,28 @@
 use bevy_ecs::component::Component;
+use bevy_ecs::entity::Entity;
 use bevy_ecs::query::QueryData;
+use bevy_ecs::query::With;
+use bevy_ecs::query::Without;
 use bevy_ecs::system::Query;
+use bevy_math::Vec3;
+use bevy_rapier3d::prelude::ExternalForce;
+use bevy_rapier3d::prelude::Sensor;
+use bevy_transform::components::Transform;
 
 use super::TimeDilation;
+use crate::collision::TrackCollisions;
 use crate::time::TIMESTEP;
 use crate::Health;
 
 const CHARGE_LOSS_FACTOR: f32 = 0.1 * TIMESTEP;
 const DISCHARGE_DAMAGE_FACTOR: f32 = 1.0;
 const DISCHARGE_THRESHOLD: f32 = 0.1;
-// TODO: Use or delete
-// const CHARGE_FORCE_FACTOR: f32 = 1.0;
+const CHARGE_FORCE_FACTOR: f32 = 1.0;
+/// Charged things further apart than this don't push or pull each other.
+const CHARGE_FORCE_RANGE: f32 = 5.0;
+/// How close we treat things as being when working out forces, so they don't
+/// get flung across the map when they touch.
+const CHARGE_FORCE_MIN_DISTANCE: f32 = 0.5;
 
 /// Charge represents electrostatic buildup, in electric potential. "Charge" is
 /// perhaps a bad name.
@@ -31,6 +43,17 @@ pub struct Charge {
 }
 
 impl Charge {
+    /// Adds `charge`, which raises potential less the more capacitance we
+    /// have. Negative charge lowers it.
+    pub fn add(&mut self, charge: f32) {
+        self.potential += charge / self.capacitance;
+    }
+
+    /// The charge we hold, as opposed to our potential.
+    pub fn charge(&self) -> f32 {
+        self.potential * self.capacitance
+    }
+
     fn tick(&mut self, time_dilation: &TimeDilation) {
         // TODO: How should charge decay? Let's just do it like temperature for
         // now.
@@ -39,16 +62,26 @@ impl Charge {
     }
 
     pub fn should_discharge(&self, other: &Charge) -> bool {
-        (self.potential - other.potential).abs() <= DISCHARGE_THRESHOLD
+        (self.potential - other.potential).abs() > DISCHARGE_THRESHOLD
     }
 
-    fn discharge(&mut self, other: &mut Charge) -> f32 {
-        let new_potential = 0.5 * (self.potential + other.potential);
-        let delta = (self.potential - new_potential).abs();
+    /// Evens out potential between us and `other`, keeping the total charge.
+    ///
+    /// Returns the damage each of us takes, from the energy we each lose.
+    fn discharge(&mut self, other: &mut Charge) -> (f32, f32) {
+        let capacitance = self.capacitance + other.capacitance;
+        if capacitance <= 0.0 {
+            return (0.0, 0.0);
+        }
+        let new_potential = (self.charge() + other.charge()) / capacitance;
+        let energy = |charge: &Charge| {
+            let delta = charge.potential - new_potential;
+            0.5 * charge.capacitance * delta * delta * DISCHARGE_DAMAGE_FACTOR
+        };
+        let damage = (energy(self), energy(other));
         self.potential = new_potential;
         other.potential = new_potential;
-
-        delta * delta * DISCHARGE_DAMAGE_FACTOR * self.capacitance
+        damage
     }
 }
 
@@ -58,27 +91,116 @@ pub fn charge_tick(mut query: Query<(&mut Charge, &TimeDilation)>) {
     }
 }
 
-// TODO: Do something with this.
 #[derive(QueryData)]
 #[query_data(mutable)]
-struct CollisionQuery {
+pub struct CollisionQuery {
     charge: &'static mut Charge,
     health: Option<&'static mut Health>,
     time_dilation: Option<&'static TimeDilation>,
 }
 
 impl CollisionQueryItem<'_> {
-    #[allow(unused)]
     fn discharge(&mut self, other: &mut CollisionQueryItem<'_>) {
-        let damage = self.charge.discharge(&mut other.charge);
+        let (damage, other_damage) = self.charge.discharge(&mut other.charge);
         self.take(damage);
-        other.take(damage);
+        other.take(other_damage);
     }
 
-    #[allow(unused)]
     fn take(&mut self, damage: f32) {
         if let (Some(h), Some(td)) = (self.health.as_mut(), self.time_dilation) {
             h.take(damage, td);
         }
     }
 }
+
+/// Discharges things that touch, if their potentials differ enough.
+///
+/// Sensors, like bullets and explosions, don't count; they aren't really
+/// touching anything.
+pub fn discharge_system(
+    tracker_q: Query<(Entity, &TrackCollisions), (With<Charge>, Without<Sensor>)>,
+    mut charge_q: Query<CollisionQuery, Without<Sensor>>,
+) {
+    for (entity, colliding) in &tracker_q {
+        for &target in &colliding.targets {
+            let Ok([mut a, mut b]) = charge_q.get_many_mut([entity, target]) else {
+                continue;
+            };
+            if a.charge.should_discharge(&b.charge) {
+                a.discharge(&mut b);
+            }
+        }
+    }
+}
+
+/// Pushes similarly charged things apart, and pulls differently charged ones
+/// together.
+pub fn force_system(
+    mut query: Query<(
+        Entity,
+        &Charge,
+        &Transform,
+        &mut ExternalForce,
+        &TimeDilation,
+    )>,
+) {
+    let mut charged = query
+        .iter()
+        .filter(|(_, charge, ..)| charge.potential.abs() > DISCHARGE_THRESHOLD)
+        .map(|(entity, charge, transform, ..)| (entity, charge.charge(), transform.translation))
+        .collect::<Vec<_>>();
+    // Sum forces in the same order every time, to stay deterministic.
+    charged.sort_by_key(|&(entity, ..)| entity);
+
+    for &(entity, charge, position) in &charged {
+        let mut force = Vec3::ZERO;
+        for &(other, other_charge, other_position) in &charged {
+            if other == entity {
+                continue;
+            }
+            let away = (position - other_position).with_y(0.0);
+            let distance = away.length();
+            if distance > CHARGE_FORCE_RANGE {
+                continue;
+            }
+            let distance = distance.max(CHARGE_FORCE_MIN_DISTANCE);
+            // Coulomb's law; like charges give a positive product, and repel.
+            force += away.normalize_or_zero() * CHARGE_FORCE_FACTOR * charge * other_charge
+                / (distance * distance);
+        }
+
+        if let Ok((_, _, _, mut external, dilation)) = query.get_mut(entity) {
+            external.force += force * dilation.factor();
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::Charge;
+
+    fn charge(potential: f32) -> Charge {
+        Charge {
+            potential,
+            capacitance: 1.0,
+        }
+    }
+
+    #[test]
+    fn discharges_only_when_different() {
+        assert!(!charge(1.0).should_discharge(&charge(1.05)));
+        assert!(charge(1.0).should_discharge(&charge(-1.0)));
+        assert!(charge(1.0).should_discharge(&charge(0.0)));
+    }
+
+    #[test]
+    fn discharge_keeps_charge() {
+        let (mut a, mut b) = (charge(2.0), charge(-1.0));
+        let (a_damage, b_damage) = a.discharge(&mut b);
+        assert_eq!(a.potential, 0.5);
+        assert_eq!(b.potential, 0.5);
+        // 0.5 * C * V^2, for the potential each lost.
+        assert_eq!(a_damage, 0.5 * 1.5 * 1.5);
+        assert_eq!(b_damage, 0.5 * 1.5 * 1.5);
+    }
+}
@@ -1,8 +1,11 @@
 use bevy_ecs::component::Component;
+use bevy_ecs::query::Without;
 use bevy_ecs::system::Query;
+use bevy_rapier3d::prelude::Velocity;
 use smallvec::SmallVec;
 
 use super::Effect;
+use crate::movement::DesiredMove;
 use crate::time::Dur;
 use crate::Libm;
 
@@ -17,6 +20,7 @@ use crate::Libm;
 #[derive(Component, Debug)]
 pub struct TimeDilation {
     val: f32,
+    prev: f32,
     effects: SmallVec<Effect, 2>,
 }
 
@@ -24,6 +28,7 @@ impl Default for TimeDilation {
     fn default() -> Self {
         Self {
             val: 1.0,
+            prev: 1.0,
             effects: Default::default(),
         }
     }
@@ -32,6 +37,7 @@ impl Default for TimeDilation {
 impl TimeDilation {
     pub const NONE: TimeDilation = TimeDilation {
         val: 1.0,
+        prev: 1.0,
         effects: SmallVec::new(),
     };
 
@@ -46,6 +52,11 @@ impl TimeDilation {
         self.val
     }
 
+    /// How much `factor` changed in the last tick, as a ratio.
+    pub fn change(&self) -> f32 {
+        self.val / self.prev
+    }
+
     fn tick(&mut self) {
         // TODO: Should this be sum? product? something else?
         let effect: f32 = self.effects.iter().map(|e| e.amount).sum();
@@ -56,6 +67,7 @@ impl TimeDilation {
         self.effects
             .retain(|e| !e.duration.tick(&TimeDilation::NONE));
 
+        self.prev = self.val;
         // Let's do exponential for < 0, linear for > 0 for now. Figure out
         // something that makes sense later.
         self.val = if effect.is_sign_negative() {
@@ -71,3 +83,16 @@ pub fn time_dilation_tick(mut query: Query<&mut TimeDilation>) {
         dilation.tick();
     }
 }
+
+/// Things that move under their own power account for time dilation when they
+/// do. Everything else, like projectiles, just keeps its velocity, so we scale
+/// it whenever its time dilation changes.
+pub fn dilate_velocity(mut query: Query<(&mut Velocity, &TimeDilation), Without<DesiredMove>>) {
+    for (mut velocity, dilation) in &mut query {
+        let change = dilation.change();
+        if change != 1.0 {
+            velocity.linvel *= change;
+            velocity.angvel *= change;
+        }
+    }
+}
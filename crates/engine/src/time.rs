@@ -6,6 +6,8 @@ use bevy_ecs::system::ResMut;
 use bevy_ecs::system::Resource;
 use bevy_reflect::Reflect;
 use bevy_utils::Duration;
+use serde::Deserialize;
+use serde::Serialize;
 use tracing::info;
 
 use crate::status_effect::TimeDilation;
@@ -26,7 +28,8 @@ impl Frame {
 }
 
 /// Represents a duration in ticks rather than time.
-#[derive(Default, Debug, Copy, Clone, Reflect, PartialEq)]
+#[derive(Default, Debug, Copy, Clone, Reflect, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct Dur(f32);
 
 impl Dur {
@@ -42,6 +45,11 @@ impl Dur {
         self.0.is_sign_positive()
     }
 
+    /// This duration less `other`, stopping at zero.
+    pub fn saturating_sub(self, other: Dur) -> Dur {
+        Self((self.0 - other.0).max(0.0))
+    }
+
     /// Tick down this duration, returning `true` if it has finished.
     pub fn tick(&mut self, time_dilation: &TimeDilation) -> bool {
         self.0 = (self.0 - time_dilation.factor()).max(0.0);
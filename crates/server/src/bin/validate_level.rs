@@ -0,0 +1,142 @@
+//! Check level images for problems, without running the game.
+//!
+//! Exits with a failure if any level has an error, so it can be used in
+//! scripts.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::sync::RwLock;
+use std::time::Duration;
+use std::time::Instant;
+
+use bevy_app::App;
+use bevy_internal::prelude::MinimalPlugins;
+use bevy_state::app::AppExtStates;
+use bevy_state::app::StatesPlugin;
+use bevy_transform::TransformPlugin;
+use clap::Parser;
+use engine::level::validate::check_grid;
+use engine::level::validate::check_nav_mesh;
+use engine::level::validate::Problem;
+use engine::level::validate::Severity;
+use engine::level::LevelGrid;
+use engine::level::LevelSource;
+use engine::run::Run;
+use engine::run::Stage;
+use engine::run::StageGoal;
+use engine::AppState;
+use oxidized_navigation::tiles::NavMeshTiles;
+use oxidized_navigation::NavMesh;
+use oxidized_navigation::NavMeshSettings;
+
+/// How many updates the navmesh needs to stay the same before we consider it
+/// built.
+const STABLE_UPDATES: u32 = 30;
+
+#[derive(Parser)]
+struct Args {
+    /// The level images to check
+    #[arg(required = true)]
+    levels: Vec<PathBuf>,
+    /// Treat warnings as errors
+    #[arg(long)]
+    strict: bool,
+    /// How many seconds to wait for the navmesh to build
+    #[arg(long, default_value_t = 30)]
+    timeout: u64,
+}
+
+fn main() -> ExitCode {
+    let args = Args::parse();
+    let timeout = Duration::from_secs(args.timeout);
+
+    let mut failed = false;
+    for path in &args.levels {
+        let problems = match LevelGrid::load(path) {
+            Ok(grid) => validate(grid, timeout),
+            Err(err) => vec![Problem::error(format!("could not load level: {err}"))],
+        };
+
+        println!("{}: {} problem(s)", path.display(), problems.len());
+        for problem in &problems {
+            println!("  {problem}");
+            if problem.severity == Severity::Error || args.strict {
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
+
+fn validate(grid: LevelGrid, timeout: Duration) -> Vec<Problem> {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin, TransformPlugin))
+        .insert_resource(Run::new(vec![Stage {
+            level: LevelSource::Grid(grid.clone()),
+            goal: StageGoal::Endless,
+        }]))
+        .add_plugins(engine::GamPlugin)
+        // We only want the level and its navmesh; nothing should be playing.
+        .insert_state(AppState::Menu);
+    app.finish();
+    app.cleanup();
+
+    let settings = app.world().resource::<NavMeshSettings>().clone();
+    let mut problems = check_grid(&grid, &settings);
+
+    let Some(nav_mesh) = build_nav_mesh(&mut app, timeout) else {
+        problems.push(Problem::error(format!(
+            "navmesh did not stabilise within {}s",
+            timeout.as_secs()
+        )));
+        return problems;
+    };
+    match nav_mesh.read() {
+        Ok(tiles) => problems.extend(check_nav_mesh(&grid, &tiles, &settings)),
+        Err(err) => problems.push(Problem::error(format!("could not read navmesh: {err}"))),
+    }
+    problems
+}
+
+/// Run the app until the navmesh stops changing.
+///
+/// Returns `None` if we run out of time first, as a partial navmesh would
+/// make reachable spots look unreachable.
+fn build_nav_mesh(app: &mut App, timeout: Duration) -> Option<Arc<RwLock<NavMeshTiles>>> {
+    let start = Instant::now();
+    let mut polygons = 0;
+    let mut stable = 0;
+
+    while start.elapsed() < timeout {
+        app.update();
+
+        let nav_mesh = app.world().resource::<NavMesh>().get();
+        let count = match nav_mesh.read() {
+            Ok(tiles) => tiles
+                .get_tiles()
+                .values()
+                .map(|tile| tile.polygons.len())
+                .sum(),
+            Err(_) => 0,
+        };
+
+        if count > 0 && count == polygons {
+            stable += 1;
+            if stable >= STABLE_UPDATES {
+                return Some(nav_mesh);
+            }
+        } else {
+            stable = 0;
+            polygons = count;
+        }
+        std::thread::sleep(Duration::from_millis(10));
+    }
+
+    None
+}
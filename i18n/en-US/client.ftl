@@ -1,6 +1,10 @@
 fps = FPS: { $fps }
 frame_time = Engine: { $time }
 score = Score: { $score }
+stage = Stage: { $stage } / { $stages }
+run_won = Run complete!
+ammo = Ammo: { $left } | { $right }
+reloading = Reloading
 
 settings = Settings
 
@@ -25,3 +29,25 @@ msaa = Msaa
 samples = Samples
 samples_one = One
 samples_four = Four
+
+editor = Level Editor
+brush_floor = Floor
+brush_pit = Pit
+brush_short_wall = Short wall
+brush_wall = Wall
+brush_player_spawn = Player spawn
+brush_enemy_spawn = Enemy spawn
+brush_door = Door
+brush_pressure_plate = Pressure plate
+brush_switch = Switch
+brush_platform = Platform
+brush_platform_end = Platform end
+brush_short_elevator = Short elevator
+brush_elevator = Elevator
+channel = Channel
+brush_size = Brush size
+play_test = Play test
+stop_play_test = Stop play test
+save = Save
+saved = Saved to { $path }
+save_failed = Could not save: { $error }